@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::audio::Sounds;
+use crate::particles::ParticleEffect;
+use crate::utils::StateLocalSpawner;
+
+/// Impacts below this (like resting or rolling creatures) do not cause any damage.
+const MIN_IMPACT: f32 = 300_000.0;
+const CRACKS: usize = 3;
+const CRACK_WIDTH: f32 = 3.0;
+const FRAGMENT_LENGTH: f32 = 30.0;
+
+const CRACK_COLOR: Color = Color::srgb(0.3, 0.25, 0.35);
+const FRAGMENT_COLOR: Color = Color::srgb(0.8, 0.75, 1.0);
+
+pub struct BreakablePlugin;
+
+impl Plugin for BreakablePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Update, (add_cracks, (damage, show_cracks).chain()));
+    }
+}
+
+/// Makes a static `rectangle`, `plank` or `wall` break into dynamic fragments after enough impacts.
+#[derive(Component, Clone, Copy)]
+pub struct Breakable {
+    durability: f32,
+    health: f32,
+}
+
+#[derive(Component, Clone, Copy)]
+struct Crack(usize);
+
+/// A Heavy creature landing from a jump hits with an impulse of about 2 000 000.
+pub fn breakable(durability: f32) -> Breakable {
+    Breakable {
+        durability,
+        health: durability,
+    }
+}
+
+fn add_cracks(mut commands: Commands, breakables: Query<(Entity, &Transform), Added<Breakable>>) {
+    for (entity, transform) in breakables.iter() {
+        // The children are scaled by the parent, so the sizes are relative
+        let width = CRACK_WIDTH / transform.scale.x;
+        commands.entity(entity).with_children(|cb| {
+            for i in 0..CRACKS {
+                let x = ((i as f32 + 0.5) * 0.618_034).fract() - 0.5;
+                cb.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: CRACK_COLOR,
+                            custom_size: Some(Vec2::ONE),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(x * 0.8, 0.1 * (i % 2) as f32, 0.1)
+                            .with_scale(Vec3::new(width, 0.8, 1.0)),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    Crack(i),
+                ));
+            }
+        });
+    }
+}
+
+fn damage(
+    commands: Commands,
+    time: Res<Time<Physics>>,
+    mut last_step: Local<Duration>,
+    collisions: Res<Collisions>,
+    mut breakables: Query<(Entity, &mut Breakable, &Transform)>,
+    mut sounds: EventWriter<Sounds>,
+    mut effects: EventWriter<ParticleEffect>,
+) {
+    // The physics might not have been stepped this frame, don't count the same impacts twice
+    if time.elapsed() == *last_step {
+        return;
+    }
+    *last_step = time.elapsed();
+    let mut cmd = StateLocalSpawner(commands);
+    for (entity, mut breakable, transform) in breakables.iter_mut() {
+        let impact: f32 = collisions
+            .collisions_with_entity(entity)
+            .filter(|contacts| contacts.during_current_frame)
+            .map(|contacts| contacts.total_normal_impulse)
+            .sum();
+        if impact <= MIN_IMPACT {
+            continue;
+        }
+        breakable.health -= impact - MIN_IMPACT;
+        if breakable.health > 0.0 {
+            continue;
+        }
+        cmd.entity(entity).despawn_recursive();
+        sounds.send(Sounds::Glass);
+        let size = transform.scale.xy();
+        let count = (size.x / FRAGMENT_LENGTH).ceil().max(1.0);
+        let length = size.x / count;
+        for i in 0..count as usize {
+            let offset = transform.rotation * Vec3::X * (length * (i as f32 + 0.5) - size.x * 0.5);
+            let position = transform.translation + offset;
+            effects.send(ParticleEffect::Splinters(position.xy()));
+            cmd.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: FRAGMENT_COLOR,
+                        custom_size: Some(Vec2::ONE),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(position)
+                        .with_scale(Vec3::new(length * 0.95, size.y, 1.0))
+                        .with_rotation(transform.rotation),
+                    ..default()
+                },
+                RigidBody::Dynamic,
+                Collider::rectangle(1.0, 1.0),
+                ColliderDensity(0.5),
+            ));
+        }
+    }
+}
+
+fn show_cracks(
+    breakables: Query<(&Breakable, &Children), Changed<Breakable>>,
+    mut cracks: Query<(&Crack, &mut Visibility)>,
+    mut sounds: EventWriter<Sounds>,
+) {
+    for (breakable, children) in breakables.iter() {
+        let damage = 1.0 - breakable.health / breakable.durability;
+        let shown = (damage * (CRACKS + 1) as f32).floor() as usize;
+        let mut iter = cracks.iter_many_mut(children);
+        while let Some((Crack(i), mut visibility)) = iter.fetch_next() {
+            if *i < shown && *visibility == Visibility::Hidden {
+                *visibility = Visibility::Inherited;
+                sounds.send(Sounds::Click);
+            } else if *i >= shown && *visibility != Visibility::Hidden {
+                // Repaired by rewinding
+                *visibility = Visibility::Hidden;
+            }
+        }
+    }
+}
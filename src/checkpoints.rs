@@ -0,0 +1,231 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::audio::Sounds;
+use crate::creature::{Creature, CreatureEvent, MainCreature};
+use crate::keys::{Key, Lock};
+use crate::levels::Level;
+use crate::objects::{spawn_pressure_plate, Door, DoorSources, Glass, PressurePlateEvent};
+use crate::particles::ParticleEffect;
+use crate::props::Prop;
+use crate::results::playing;
+use crate::ui::Signal;
+use crate::utils::StateLocalSpawner;
+
+const POLE_HEIGHT: f32 = 60.0;
+const FLAG_SIZE: Vec2 = Vec2::new(30.0, 20.0);
+
+const POLE_COLOR: Color = Color::srgb(0.4, 0.35, 0.3);
+const FLAG_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+const REACHED_COLOR: Color = Color::srgb(0.2, 0.8, 0.4);
+
+pub struct CheckpointPlugin;
+
+impl Plugin for CheckpointPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.init_resource::<LastCheckpoint>().add_systems(
+            Update,
+            (
+                reset_checkpoint,
+                take_snapshot,
+                restore_snapshot.run_if(playing),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// The state of the level when a checkpoint plate was last pressed.
+#[derive(Resource, Default)]
+pub struct LastCheckpoint(Option<Snapshot>);
+
+struct Snapshot {
+    main: Option<Entity>,
+    bodies: Vec<(Entity, Transform)>,
+    joints: Vec<FixedJoint>,
+    doors: Vec<(Entity, Door, Transform)>,
+    glass: Vec<(Entity, RigidBody, Transform)>,
+}
+
+#[derive(Component, Clone, Copy)]
+struct Checkpoint {
+    flag: Entity,
+}
+
+/// A plate that saves the progress, with a flag that lights up when reached.
+pub fn spawn_checkpoint(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    center: Vec2,
+    width: f32,
+    rotation: f32,
+) {
+    let plate = spawn_pressure_plate(commands, Signal::Checkpoint, center, width, rotation);
+    let rotation = Quat::from_rotation_z(rotation);
+    let offset = rotation * Vec3::new(width * 0.5, POLE_HEIGHT * 0.5, 0.0);
+    let mut flag = None;
+    commands
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                color: POLE_COLOR,
+                custom_size: Some(Vec2::new(4.0, POLE_HEIGHT)),
+                ..default()
+            },
+            transform: Transform::from_translation(center.extend(-1.1) + offset)
+                .with_rotation(rotation),
+            ..default()
+        })
+        .with_children(|cb| {
+            flag = Some(
+                cb.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: FLAG_COLOR,
+                        custom_size: Some(FLAG_SIZE),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(
+                        -FLAG_SIZE.x * 0.5,
+                        (POLE_HEIGHT - FLAG_SIZE.y) * 0.5,
+                        0.0,
+                    ),
+                    ..default()
+                })
+                .id(),
+            );
+        });
+    if let Some(flag) = flag {
+        commands.entity(plate).insert(Checkpoint { flag });
+    }
+}
+
+fn reset_checkpoint(
+    mut transitions: EventReader<StateTransitionEvent<Level>>,
+    mut checkpoint: ResMut<LastCheckpoint>,
+) {
+    if transitions.read().count() > 0 {
+        checkpoint.0 = None;
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn take_snapshot(
+    mut events: EventReader<PressurePlateEvent>,
+    mut checkpoint: ResMut<LastCheckpoint>,
+    checkpoints: Query<&Checkpoint>,
+    mut flags: Query<&mut Sprite>,
+    main: Query<Entity, (With<MainCreature>, With<Creature>)>,
+    bodies: Query<(Entity, &Transform), Or<(With<Creature>, With<Prop>, With<Key>)>>,
+    joints: Query<&FixedJoint>,
+    creatures: Query<(), With<Creature>>,
+    doors: Query<(Entity, &Door, &Transform), Without<Lock>>,
+    glass: Query<(Entity, &RigidBody, &Transform), With<Glass>>,
+    mut sounds: EventWriter<Sounds>,
+) {
+    for PressurePlateEvent(plate, signal, pressed) in events.read() {
+        let (Signal::Checkpoint, true) = (signal, pressed) else {
+            continue;
+        };
+        if let Ok(Checkpoint { flag }) = checkpoints.get(*plate) {
+            if let Ok(mut sprite) = flags.get_mut(*flag) {
+                if sprite.color != REACHED_COLOR {
+                    sprite.color = REACHED_COLOR;
+                    sounds.send(Sounds::Hello);
+                }
+            }
+        }
+        checkpoint.0 = Some(Snapshot {
+            main: main.get_single().ok(),
+            bodies: bodies.iter().map(|(e, t)| (e, *t)).collect(),
+            joints: joints
+                .iter()
+                .filter(|j| creatures.contains(j.entity1) || creatures.contains(j.entity2))
+                .copied()
+                .collect(),
+            doors: doors.iter().map(|(e, d, t)| (e, d.clone(), *t)).collect(),
+            glass: glass.iter().map(|(e, rb, t)| (e, *rb, *t)).collect(),
+        });
+    }
+}
+
+/// Everything after the checkpoint is undone without reloading the level,
+/// or the level is restarted if no checkpoint has been reached.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn restore_snapshot(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    checkpoint: Res<LastCheckpoint>,
+    state: Res<State<Level>>,
+    mut next_state: ResMut<NextState<Level>>,
+    joints: Query<(Entity, &FixedJoint)>,
+    creatures: Query<&Creature>,
+    mut bodies: Query<(
+        &mut Transform,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+        Option<&mut RigidBody>,
+        Option<&mut Door>,
+    )>,
+    sources: DoorSources,
+    mut events: EventWriter<CreatureEvent>,
+    mut sounds: EventWriter<Sounds>,
+    mut effects: EventWriter<ParticleEffect>,
+) {
+    if !keyboard_input.any_just_pressed([KeyCode::KeyC, KeyCode::Backspace]) {
+        return;
+    }
+    let Some(snapshot) = &checkpoint.0 else {
+        next_state.set(*state.get());
+        return;
+    };
+    for (entity, joint) in joints.iter() {
+        if creatures.contains(joint.entity1) || creatures.contains(joint.entity2) {
+            commands.entity(entity).despawn();
+        }
+    }
+    for joint in snapshot.joints.iter() {
+        if bodies.contains(joint.entity1) && bodies.contains(joint.entity2) {
+            commands.spawn(*joint);
+        }
+    }
+    let stored = snapshot
+        .bodies
+        .iter()
+        .map(|(e, t)| (*e, *t, None, None))
+        .chain(
+            snapshot
+                .doors
+                .iter()
+                .map(|(e, d, t)| (*e, *t, None, Some(d))),
+        )
+        .chain(
+            snapshot
+                .glass
+                .iter()
+                .map(|(e, rb, t)| (*e, *t, Some(*rb), None)),
+        );
+    for (entity, transform, rb, door) in stored {
+        let Ok((mut current, mut velocity, mut angular, current_rb, current_door)) =
+            bodies.get_mut(entity)
+        else {
+            continue;
+        };
+        *current = transform;
+        velocity.0 = Vec2::ZERO;
+        angular.0 = 0.0;
+        if let (Some(rb), Some(mut current_rb)) = (rb, current_rb) {
+            *current_rb = rb;
+        }
+        if let (Some(door), Some(mut current_door)) = (door, current_door) {
+            current_door.restore(door, &sources);
+        }
+        if let Ok(creature) = creatures.get(entity) {
+            effects.send(ParticleEffect::Burst(
+                transform.translation.xy(),
+                creature.color(),
+            ));
+        }
+    }
+    if let Some(main) = snapshot.main {
+        events.send(CreatureEvent::Restored(main));
+    }
+    sounds.send(Sounds::Hello);
+}
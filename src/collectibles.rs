@@ -0,0 +1,251 @@
+use std::f32::consts::FRAC_PI_4;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::audio::Sounds;
+use crate::creature::Creature;
+use crate::levels::Level;
+use crate::objects::PressurePlateEvent;
+use crate::particles::ParticleEffect;
+use crate::save::SaveData;
+use crate::ui::{Signal, TextStyles};
+use crate::utils::StateLocalSpawner;
+
+const COLLECTIBLE_RADIUS: f32 = 15.0;
+const BOB_HEIGHT: f32 = 4.0;
+const BOB_SPEED: f32 = 3.0;
+
+const SHELL_COLOR: Color = Color::srgb(1.0, 0.75, 0.7);
+const CONE_COLOR: Color = Color::srgb(0.85, 0.6, 0.3);
+const ICE_CREAM_COLOR: Color = Color::srgb(1.0, 0.85, 0.9);
+const SUNGLASSES_COLOR: Color = Color::srgb(0.1, 0.1, 0.15);
+
+pub struct CollectiblePlugin;
+
+impl Plugin for CollectiblePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.init_resource::<LevelCollectibles>().add_systems(
+            Update,
+            (
+                reset_collectibles,
+                count_collectibles,
+                pick_up,
+                record_collectibles,
+                update_hud,
+                bob,
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Collectible {
+    Shell,
+    IceCream,
+    Sunglasses,
+}
+
+impl Collectible {
+    fn color(self) -> Color {
+        match self {
+            Collectible::Shell => SHELL_COLOR,
+            Collectible::IceCream => ICE_CREAM_COLOR,
+            Collectible::Sunglasses => SUNGLASSES_COLOR,
+        }
+    }
+}
+
+/// The collectibles of the current level.
+#[derive(Resource, Clone, Copy, Default)]
+pub struct LevelCollectibles {
+    pub collected: u32,
+    pub total: u32,
+}
+
+#[derive(Component, Clone, Copy)]
+struct CollectibleVisual {}
+
+#[derive(Component, Clone, Copy)]
+struct CollectibleHud {}
+
+fn square(color: Color, size: Vec2, position: Vec2, rotation: f32) -> SpriteBundle {
+    SpriteBundle {
+        sprite: Sprite {
+            color,
+            custom_size: Some(size),
+            ..default()
+        },
+        transform: Transform::from_translation(position.extend(0.0))
+            .with_rotation(Quat::from_rotation_z(rotation)),
+        ..default()
+    }
+}
+
+pub fn spawn_collectible(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    kind: Collectible,
+    position: Vec2,
+) {
+    commands
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_translation(position.extend(0.8))),
+            RigidBody::Static,
+            Collider::circle(COLLECTIBLE_RADIUS),
+            Sensor,
+            kind,
+        ))
+        .with_children(|cb| {
+            cb.spawn((SpatialBundle::default(), CollectibleVisual {}))
+                .with_children(|cb| match kind {
+                    Collectible::Shell => {
+                        cb.spawn(square(
+                            SHELL_COLOR,
+                            Vec2::splat(18.0),
+                            Vec2::ZERO,
+                            FRAC_PI_4,
+                        ));
+                        cb.spawn(square(
+                            SHELL_COLOR.darker(0.15),
+                            Vec2::new(3.0, 20.0),
+                            Vec2::ZERO,
+                            0.0,
+                        ));
+                    }
+                    Collectible::IceCream => {
+                        cb.spawn(square(
+                            CONE_COLOR,
+                            Vec2::new(10.0, 10.0),
+                            Vec2::new(0.0, -8.0),
+                            FRAC_PI_4,
+                        ));
+                        cb.spawn(square(
+                            ICE_CREAM_COLOR,
+                            Vec2::splat(14.0),
+                            Vec2::new(0.0, 3.0),
+                            0.0,
+                        ));
+                    }
+                    Collectible::Sunglasses => {
+                        for x in [-7.0, 7.0] {
+                            cb.spawn(square(
+                                SUNGLASSES_COLOR,
+                                Vec2::new(11.0, 8.0),
+                                Vec2::new(x, 0.0),
+                                0.0,
+                            ));
+                        }
+                        cb.spawn(square(
+                            SUNGLASSES_COLOR,
+                            Vec2::new(26.0, 2.0),
+                            Vec2::new(0.0, 3.0),
+                            0.0,
+                        ));
+                    }
+                });
+        });
+}
+
+fn reset_collectibles(
+    mut transitions: EventReader<StateTransitionEvent<Level>>,
+    mut collectibles: ResMut<LevelCollectibles>,
+) {
+    if transitions.read().count() > 0 {
+        *collectibles = LevelCollectibles::default();
+    }
+}
+
+fn count_collectibles(
+    added: Query<(), Added<Collectible>>,
+    mut collectibles: ResMut<LevelCollectibles>,
+) {
+    let count = added.iter().count() as u32;
+    if count > 0 {
+        collectibles.total += count;
+    }
+}
+
+fn pick_up(
+    mut commands: Commands,
+    mut collision_event_reader: EventReader<CollisionStarted>,
+    items: Query<(&Collectible, &GlobalTransform)>,
+    creatures: Query<(), With<Creature>>,
+    mut collectibles: ResMut<LevelCollectibles>,
+    mut sounds: EventWriter<Sounds>,
+    mut effects: EventWriter<ParticleEffect>,
+) {
+    // Several creatures of a group can touch the same item at once
+    let mut picked = HashSet::new();
+    for CollisionStarted(e1, e2) in collision_event_reader.read() {
+        for (item, creature) in [(e1, e2), (e2, e1)] {
+            if creatures.contains(*creature) && items.contains(*item) {
+                picked.insert(*item);
+            }
+        }
+    }
+    for item in picked {
+        let Ok((kind, transform)) = items.get(item) else {
+            continue;
+        };
+        commands.entity(item).despawn_recursive();
+        collectibles.collected += 1;
+        sounds.send(Sounds::Click);
+        effects.send(ParticleEffect::Burst(
+            transform.translation().xy(),
+            kind.color(),
+        ));
+    }
+}
+
+fn record_collectibles(
+    mut events: EventReader<PressurePlateEvent>,
+    collectibles: Res<LevelCollectibles>,
+    state: Res<State<Level>>,
+    mut save: ResMut<SaveData>,
+) {
+    for PressurePlateEvent(_, signal, pressed) in events.read() {
+        if let (Signal::NextLevel, true) = (signal, pressed) {
+            if collectibles.total > 0 {
+                save.record_collectibles(*state.get(), collectibles.collected, collectibles.total);
+            }
+        }
+    }
+}
+
+fn collectible_text(collected: u32, total: u32) -> String {
+    format!("Treasures {collected} / {total}")
+}
+
+fn update_hud(
+    commands: Commands,
+    collectibles: Res<LevelCollectibles>,
+    mut huds: Query<&mut Text, With<CollectibleHud>>,
+    text_styles: Res<TextStyles>,
+) {
+    if !collectibles.is_changed() || collectibles.total == 0 {
+        return;
+    }
+    let text = collectible_text(collectibles.collected, collectibles.total);
+    if let Ok(mut hud) = huds.get_single_mut() {
+        hud.sections[0].value = text;
+    } else {
+        StateLocalSpawner(commands).spawn((
+            TextBundle::from_section(text, text_styles.sign_text.clone()).with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            }),
+            CollectibleHud {},
+        ));
+    }
+}
+
+fn bob(time: Res<Time>, mut visuals: Query<&mut Transform, With<CollectibleVisual>>) {
+    let y = (time.elapsed_seconds() * BOB_SPEED).sin() * BOB_HEIGHT;
+    for mut transform in visuals.iter_mut() {
+        transform.translation.y = y;
+    }
+}
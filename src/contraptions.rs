@@ -0,0 +1,234 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::objects::PressurePlateEvent;
+use crate::ui::Signal;
+use crate::utils::StateLocalSpawner;
+
+const CONTRAPTION_THICKNESS: f32 = 15.0;
+const BRIDGE_THICKNESS: f32 = 10.0;
+const BRIDGE_GAP: f32 = 4.0;
+const TRAPDOOR_SPEED: f32 = 3.0;
+const TRAPDOOR_SNAP: f32 = 0.02;
+
+const CONTRAPTION_COLOR: Color = Color::srgb(0.75, 0.6, 0.45);
+const PIVOT_COLOR: Color = Color::srgb(0.4, 0.35, 0.3);
+
+pub struct ContraptionPlugin;
+
+impl Plugin for ContraptionPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Update, (trapdoor_signals, lock_trapdoors).chain());
+    }
+}
+
+/// A hinged plank that is locked closed, except while its `Signal::Custom` channel is pressed.
+#[derive(Component, Clone)]
+pub struct Trapdoor {
+    hinge: Vec2,
+    channel: u16,
+    pressed: u16,
+}
+
+fn beam(center: Vec2, size: Vec2, rotation: f32, density: f32) -> impl Bundle {
+    (
+        SpriteBundle {
+            sprite: Sprite {
+                color: CONTRAPTION_COLOR,
+                custom_size: Some(size),
+                ..default()
+            },
+            transform: Transform::from_translation(center.extend(-0.8))
+                .with_rotation(Quat::from_rotation_z(rotation)),
+            ..default()
+        },
+        RigidBody::Dynamic,
+        Collider::rectangle(size.x, size.y),
+        ColliderDensity(density),
+        Friction::new(1.0),
+    )
+}
+
+fn pivot(position: Vec2) -> impl Bundle {
+    (
+        SpriteBundle {
+            sprite: Sprite {
+                color: PIVOT_COLOR,
+                custom_size: Some(Vec2::splat(CONTRAPTION_THICKNESS * 0.6)),
+                ..default()
+            },
+            transform: Transform::from_translation(position.extend(-0.7))
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+            ..default()
+        },
+        RigidBody::Static,
+    )
+}
+
+/// A plank of `length` balancing on `pivot_point`, tilting at most `max_angle` radians either way.
+pub fn spawn_seesaw(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    pivot_point: Vec2,
+    length: f32,
+    max_angle: f32,
+) {
+    let anchor = commands.spawn(pivot(pivot_point)).id();
+    let center = pivot_point + Vec2::Y * CONTRAPTION_THICKNESS * 0.5;
+    let plank = commands
+        .spawn(beam(
+            center,
+            Vec2::new(length, CONTRAPTION_THICKNESS),
+            0.0,
+            1.0,
+        ))
+        .id();
+    commands.spawn(
+        RevoluteJoint::new(anchor, plank)
+            .with_local_anchor_2(Vec2::NEG_Y * CONTRAPTION_THICKNESS * 0.5)
+            .with_angle_limits(-max_angle, max_angle),
+    );
+}
+
+/// A plank from `hinge` that swings down (clockwise for positive lengths) when unlocked.
+pub fn spawn_trapdoor(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    hinge: Vec2,
+    length: f32,
+    channel: u16,
+) {
+    let anchor = commands.spawn(pivot(hinge)).id();
+    let offset = Vec2::new(length * 0.5, -CONTRAPTION_THICKNESS * 0.5);
+    let door = commands
+        .spawn((
+            beam(
+                hinge + offset,
+                Vec2::new(length.abs(), CONTRAPTION_THICKNESS),
+                0.0,
+                1.0,
+            ),
+            Trapdoor {
+                hinge,
+                channel,
+                pressed: 0,
+            },
+        ))
+        .id();
+    // Only swing downwards from the closed position
+    let (min, max) = if length > 0.0 {
+        (-std::f32::consts::FRAC_PI_2, 0.0)
+    } else {
+        (0.0, std::f32::consts::FRAC_PI_2)
+    };
+    commands.spawn(
+        RevoluteJoint::new(anchor, door)
+            .with_local_anchor_2(-offset)
+            .with_angle_limits(min, max),
+    );
+}
+
+/// Planks between two static anchors, held together by ropes that let the bridge sag.
+pub fn spawn_rope_bridge(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    start: Vec2,
+    end: Vec2,
+    segments: usize,
+    slack: f32,
+) {
+    let segments = segments.max(1);
+    let rotation = (end - start).to_angle();
+    let step = start.distance(end) / segments as f32;
+    let width = step - BRIDGE_GAP;
+    let rope = BRIDGE_GAP * (1.0 + slack);
+    let half = Vec2::new(width * 0.5, 0.0);
+    let mut previous = commands.spawn(pivot(start)).id();
+    let mut previous_anchor = Vec2::ZERO;
+    for i in 0..segments {
+        let center = start.lerp(end, (i as f32 + 0.5) / segments as f32);
+        let plank = commands
+            .spawn(beam(
+                center,
+                Vec2::new(width, BRIDGE_THICKNESS),
+                rotation,
+                0.5,
+            ))
+            .id();
+        commands.spawn(
+            DistanceJoint::new(previous, plank)
+                .with_local_anchor_1(previous_anchor)
+                .with_local_anchor_2(-half)
+                .with_rest_length(rope)
+                .with_limits(0.0, rope),
+        );
+        previous = plank;
+        previous_anchor = half;
+    }
+    let anchor = commands.spawn(pivot(end)).id();
+    commands.spawn(
+        DistanceJoint::new(previous, anchor)
+            .with_local_anchor_1(previous_anchor)
+            .with_rest_length(rope)
+            .with_limits(0.0, rope),
+    );
+}
+
+fn trapdoor_signals(
+    mut events: EventReader<PressurePlateEvent>,
+    mut trapdoors: Query<&mut Trapdoor>,
+) {
+    for PressurePlateEvent(_, signal, pressed) in events.read() {
+        let Signal::Custom(i) = signal else {
+            continue;
+        };
+        for mut trapdoor in trapdoors.iter_mut() {
+            if trapdoor.channel == *i {
+                trapdoor.pressed = if *pressed {
+                    trapdoor.pressed + 1
+                } else {
+                    trapdoor.pressed.saturating_sub(1)
+                };
+            }
+        }
+    }
+}
+
+/// Locked trapdoors swing back to the closed position and then hold still.
+fn lock_trapdoors(
+    time: Res<Time>,
+    mut trapdoors: Query<(
+        &Trapdoor,
+        &mut RigidBody,
+        &mut Transform,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+) {
+    let delta_time = time.delta_seconds();
+    for (trapdoor, mut rb, mut transform, mut velocity, mut angular) in trapdoors.iter_mut() {
+        if trapdoor.pressed > 0 {
+            if !rb.is_dynamic() {
+                *rb = RigidBody::Dynamic;
+            }
+            continue;
+        }
+        let angle = transform.rotation.to_euler(EulerRot::XYZ).2;
+        let arm = transform.translation.xy() - trapdoor.hinge;
+        if angle.abs() < TRAPDOOR_SNAP || delta_time <= 0.0 {
+            if !rb.is_static() {
+                *rb = RigidBody::Static;
+                let rotation = Quat::from_rotation_z(-angle);
+                transform.rotation = rotation * transform.rotation;
+                transform.translation = (trapdoor.hinge + (rotation * arm.extend(0.0)).xy())
+                    .extend(transform.translation.z);
+                velocity.0 = Vec2::ZERO;
+                angular.0 = 0.0;
+            }
+            continue;
+        }
+        if *rb != RigidBody::Kinematic {
+            *rb = RigidBody::Kinematic;
+        }
+        // Rotate around the hinge instead of the center
+        angular.0 = -angle.signum() * TRAPDOOR_SPEED.min(angle.abs() / delta_time);
+        velocity.0 = angular.0 * arm.perp();
+    }
+}
@@ -1,14 +1,25 @@
 use avian2d::prelude::*;
-use bevy::utils::HashMap;
+use bevy::utils::{HashMap, HashSet};
 use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
 use enum_iterator::{all, Sequence};
 
 use crate::audio::Sounds;
+use crate::joints::JointGraph;
+use crate::keys::Key;
+use crate::particles::ParticleEffect;
+use crate::platforms::Conveyor;
+use crate::props::{Grabbable, Prop};
+use crate::results::playing;
 use crate::utils::StateLocalSpawner;
 
 const MAX_ANGULAR_VELOCITY: f32 = 15.0;
+const AIR_JUMP: f32 = 0.05;
+const AIR_SPEED: f32 = 0.5;
+/// Mass that a single creature moves at full speed and jump, heavier groups are slower.
+const CREATURE_STRENGTH: f32 = 2000.0;
 const ARM_WIDTH: f32 = 10.0;
-const EYE_RADIUS: f32 = 5.0;
+/// How far props are shoved, relative to the radius of the shoving creature.
+const SHOVE_RANGE: f32 = 3.0;
 
 pub struct CreaturePlugin;
 
@@ -18,17 +29,17 @@ impl Plugin for CreaturePlugin {
             .add_systems(
                 Update,
                 (
-                    movement,
-                    jump,
-                    explode,
+                    (movement, jump, explode, switch_main).run_if(playing),
                     on_collision_enter,
                     on_collision_exit,
                     arms,
                     on_spread_control,
-                    switch_main,
+                    on_poofed,
+                    on_restored,
                 ),
             )
-            .init_gizmo_group::<ArmGizmos>();
+            .init_gizmo_group::<ArmGizmos>()
+            .add_event::<CreatureEvent>();
     }
 }
 
@@ -93,11 +104,22 @@ impl Creature {
     }
 }
 
+#[derive(Event, Debug, Clone, Copy)]
+pub enum CreatureEvent {
+    Joined(Entity, Entity),
+    Jumped(Entity),
+    Shoved(Entity),
+    /// Sent back to its spawn point by a hazard.
+    Poofed(Entity),
+    /// Everything was returned to a checkpoint, with this as the main creature.
+    Restored(Entity),
+    /// Became the main creature by switching.
+    Switched(Entity),
+}
+
 #[derive(Resource)]
 pub struct CreatureAssets {
     map: HashMap<Creature, (Handle<Mesh>, Handle<ColorMaterial>)>,
-    eye_mesh: Handle<Mesh>,
-    eye_material: Handle<ColorMaterial>,
     mouth_sprite: Handle<Image>,
 }
 
@@ -123,17 +145,27 @@ fn setup(
                 )
             })
             .collect(),
-        eye_mesh: meshes.add(Circle::new(EYE_RADIUS)),
-        eye_material: materials.add(Color::WHITE),
-        // tongue_sprite: asset_server.load("sprites/tongue.png"),
         mouth_sprite: asset_server.load("sprites/mouth.png"),
     };
     commands.insert_resource(assets);
     config_store.config_mut::<ArmGizmos>().0.line_width = ARM_WIDTH;
 }
 
+/// Everything that is touching the creature, except other creatures and held props.
+#[derive(Component, Clone, Default)]
+pub struct Grounded(HashSet<Entity>);
+
+impl Grounded {
+    pub fn is_grounded(&self) -> bool {
+        !self.0.is_empty()
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+pub struct CreatureBody {}
+
 #[derive(Component, Clone, Copy)]
-struct Grounded(u32);
+pub struct SpawnPoint(pub Vec2);
 
 #[derive(Component, Clone, Copy)]
 pub struct MainCreature {}
@@ -176,80 +208,174 @@ impl Creature {
             .get(&species)
             .expect("All creature assets should have been initialised in the setup");
         let mut ec = commands.spawn((
-            MaterialMesh2dBundle {
-                mesh: mesh.clone().into(),
-                material: material.clone(),
-                transform: Transform::from_xyz(x, y, 1.0),
-                ..default()
-            },
+            SpatialBundle::from_transform(Transform::from_xyz(x, y, 1.0)),
             RigidBody::Dynamic,
             ColliderDensity(species.density()),
             Collider::circle(species.radius()),
             Restitution::new(species.bounciness()),
             Friction::new(2.5),
             species,
-            Grounded(0),
+            Grounded::default(),
+            SpawnPoint(Vec2::new(x, y)),
         ));
         if controlled {
             ec.insert((MainCreature {}, SpreadControl {}));
         }
         ec.with_children(|cb| {
-            cb.spawn(MaterialMesh2dBundle {
-                mesh: assets.eye_mesh.clone().into(),
-                material: assets.eye_material.clone(),
-                transform: Transform::from_xyz(species.radius() * 0.4, species.radius() * 0.4, 1.1),
-                ..default()
-            });
-            cb.spawn(MaterialMesh2dBundle {
-                mesh: assets.eye_mesh.clone().into(),
-                material: assets.eye_material.clone(),
-                transform: Transform::from_xyz(
-                    -species.radius() * 0.4,
-                    species.radius() * 0.4,
-                    1.1,
-                ),
-                ..default()
-            });
+            cb.spawn((
+                MaterialMesh2dBundle {
+                    mesh: mesh.clone().into(),
+                    material: material.clone(),
+                    ..default()
+                },
+                CreatureBody {},
+            ));
         });
         ec.id()
     }
 }
 
+/// Combined movement of a connected group, weighted by the mass of each creature
+/// and slowed down by the mass of the whole group.
+struct GroupMotion {
+    members: Vec<Entity>,
+    grounded: bool,
+    jump: f32,
+    speed: f32,
+}
+
+fn group_motions(
+    graph: &JointGraph,
+    creatures: &Query<(Entity, &Creature, &Grounded), With<Controlled>>,
+    masses: &Query<&Mass>,
+) -> Vec<GroupMotion> {
+    let mut seen = HashSet::new();
+    let mut motions = Vec::new();
+    for (entity, _, _) in creatures.iter() {
+        if seen.contains(&entity) {
+            continue;
+        }
+        let members = graph.group_of(entity);
+        seen.extend(members.iter().copied());
+        // Carried props and uncontrolled creatures do not push, but still have to be moved
+        let pushing: Vec<_> = creatures.iter_many(&members).collect();
+        let pushing_mass: f32 = pushing
+            .iter()
+            .filter_map(|(e, _, _)| masses.get(*e).ok())
+            .map(|m| m.0)
+            .sum();
+        let group_mass = graph.group_mass(entity, masses);
+        if pushing_mass <= 0.0 || group_mass <= 0.0 {
+            continue;
+        }
+        let strength = (pushing.len() as f32 * CREATURE_STRENGTH / group_mass).min(1.0);
+        let mut motion = GroupMotion {
+            members: Vec::new(),
+            grounded: false,
+            jump: 0.0,
+            speed: 0.0,
+        };
+        for (e, creature, grounded) in pushing {
+            let weight = masses.get(e).map_or(0.0, |m| m.0) / pushing_mass * strength;
+            motion.grounded |= grounded.is_grounded();
+            motion.jump += creature.jump() * weight;
+            motion.speed += creature.speed() * weight;
+        }
+        motion.members = members;
+        motions.push(motion);
+    }
+    motions
+}
+
+#[allow(clippy::too_many_arguments)]
 fn jump(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut creatures: Query<(&mut LinearVelocity, &Creature, &Grounded), With<Controlled>>,
+    graph: Res<JointGraph>,
+    creatures: Query<(Entity, &Creature, &Grounded), With<Controlled>>,
+    masses: Query<&Mass>,
+    mut velocities: Query<(&mut LinearVelocity, &RigidBody)>,
+    transforms: Query<&GlobalTransform>,
+    mut events: EventWriter<CreatureEvent>,
+    mut effects: EventWriter<ParticleEffect>,
 ) {
     if keyboard_input.any_just_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) {
-        for (mut linear_velocity, creature, grounded) in &mut creatures {
-            if grounded.0 > 0 {
-                linear_velocity.y += creature.jump();
-                // TODO FX
+        for motion in group_motions(&graph, &creatures, &masses) {
+            let delta = if motion.grounded {
+                for (e, creature, _) in creatures.iter_many(&motion.members) {
+                    events.send(CreatureEvent::Jumped(e));
+                    if let Ok(transform) = transforms.get(e) {
+                        let feet = transform.translation().xy() - Vec2::Y * creature.radius();
+                        effects.send(ParticleEffect::Dust(feet));
+                    }
+                }
+                motion.jump
             } else {
-                linear_velocity.y += creature.jump() * 0.05;
+                motion.jump * AIR_JUMP
+            };
+            for e in motion.members {
+                if let Ok((mut linear_velocity, rb)) = velocities.get_mut(e) {
+                    if rb.is_dynamic() {
+                        linear_velocity.y += delta;
+                    }
+                }
             }
         }
     }
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn explode(
     mut commands: Commands,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut creatures: Query<(&mut LinearVelocity, &GlobalTransform, &Creature)>,
-    joints: Query<(Entity, &FixedJoint)>,
+    mut props: Query<
+        (&mut LinearVelocity, &GlobalTransform, &Mass),
+        (Or<(With<Prop>, With<Key>)>, Without<Creature>),
+    >,
+    masses: Query<&Mass, With<Creature>>,
+    graph: Res<JointGraph>,
     controls: Query<(Entity, Option<&MainCreature>), (With<Controlled>, With<Creature>)>,
     mouths: Query<(Entity, &Parent), With<Mouth>>,
     mut sounds: EventWriter<Sounds>,
+    mut events: EventWriter<CreatureEvent>,
+    mut effects: EventWriter<ParticleEffect>,
 ) {
     if keyboard_input.any_just_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) {
-        for (entity, joint) in joints.iter() {
-            if let Ok((_, transform1, creature1)) = creatures.get(joint.entity1) {
-                if let Ok((_, transform2, creature2)) = creatures.get(joint.entity2) {
+        for (entity, entity1, entity2) in graph.joints() {
+            let held = (creatures.contains(entity1) && props.contains(entity2))
+                || (creatures.contains(entity2) && props.contains(entity1));
+            if held {
+                // Let go of held props and keys, they are shoved below
+                commands.entity(entity).despawn();
+            } else if let Ok((_, transform1, creature1)) = creatures.get(entity1) {
+                if let Ok((_, transform2, creature2)) = creatures.get(entity2) {
                     commands.entity(entity).despawn();
                     let dir = transform2.translation().xy() - transform1.translation().xy();
                     let dir = dir.normalize() * (creature1.force() + creature2.force());
-                    creatures.get_mut(joint.entity1).unwrap().0 .0 -= dir;
-                    creatures.get_mut(joint.entity2).unwrap().0 .0 += dir;
-                    // TODO FX
+                    effects.send(ParticleEffect::Burst(
+                        transform1.translation().xy(),
+                        creature1.color(),
+                    ));
+                    effects.send(ParticleEffect::Burst(
+                        transform2.translation().xy(),
+                        creature2.color(),
+                    ));
+                    creatures.get_mut(entity1).unwrap().0 .0 -= dir;
+                    creatures.get_mut(entity2).unwrap().0 .0 += dir;
+                    events.send(CreatureEvent::Shoved(entity1));
+                    events.send(CreatureEvent::Shoved(entity2));
+                }
+            }
+        }
+        for (e, _) in controls.iter() {
+            let (_, transform, creature) = creatures.get(e).unwrap();
+            let center = transform.translation().xy();
+            let mass = masses.get(e).map_or(0.0, |m| m.0);
+            for (mut velocity, prop_transform, prop_mass) in props.iter_mut() {
+                let dir = prop_transform.translation().xy() - center;
+                if prop_mass.0 > 0.0 && dir.length() < creature.radius() * SHOVE_RANGE {
+                    let speed = (creature.force() * mass / prop_mass.0).min(creature.force() * 2.0);
+                    velocity.0 += dir.normalize_or_zero() * speed;
                 }
             }
         }
@@ -270,75 +396,129 @@ fn explode(
 fn movement(
     time: Res<Time>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut creatures: Query<(&mut AngularVelocity, &Creature, &Grounded), With<Controlled>>,
+    graph: Res<JointGraph>,
+    creatures: Query<(Entity, &Creature, &Grounded), With<Controlled>>,
+    masses: Query<&Mass>,
+    conveyors: Query<(&Conveyor, &Transform)>,
+    mut angular_velocities: Query<(&mut AngularVelocity, &Creature, &Grounded)>,
 ) {
     let delta_time = time.delta_seconds();
     let left = keyboard_input.any_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]);
     let right = keyboard_input.any_pressed([KeyCode::KeyD, KeyCode::ArrowRight]);
-    if left & !right {
-        for (mut angular_velocity, creature, grounded) in &mut creatures {
-            let delta = delta_time * if grounded.0 > 0 { 1.0 } else { 0.5 } * creature.speed();
-            angular_velocity.0 = MAX_ANGULAR_VELOCITY.min(angular_velocity.0 + delta);
-        }
+    let direction = if left & !right {
+        1.0
     } else if right & !left {
-        for (mut angular_velocity, creature, grounded) in &mut creatures {
-            let delta = delta_time * if grounded.0 > 0 { 1.0 } else { 0.5 } * creature.speed();
-            angular_velocity.0 = (-MAX_ANGULAR_VELOCITY).max(angular_velocity.0 - delta);
+        -1.0
+    } else {
+        return;
+    };
+    for motion in group_motions(&graph, &creatures, &masses) {
+        let delta = delta_time * if motion.grounded { 1.0 } else { AIR_SPEED } * motion.speed;
+        let mut iter = angular_velocities.iter_many_mut(&motion.members);
+        while let Some((mut angular_velocity, creature, grounded)) = iter.fetch_next() {
+            // The cap is relative to the belt the creature rolls on
+            let belt = grounded
+                .0
+                .iter()
+                .find_map(|e| conveyors.get(*e).ok())
+                .map_or(0.0, |(conveyor, transform)| {
+                    -conveyor.surface_velocity(transform).x / creature.radius()
+                });
+            angular_velocity.0 = if direction > 0.0 {
+                (belt + MAX_ANGULAR_VELOCITY).min(angular_velocity.0 + delta)
+            } else {
+                (belt - MAX_ANGULAR_VELOCITY).max(angular_velocity.0 - delta)
+            };
         }
     }
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn on_collision_enter(
     mut commands: Commands,
     mut collision_event_reader: EventReader<CollisionStarted>,
     creatures: Query<(&GlobalTransform, &Creature, Option<&Controlled>)>,
-    joints: Query<&FixedJoint>,
+    grabbables: Query<(&GlobalTransform, Has<Key>), Or<(With<Grabbable>, With<Key>)>>,
+    graph: Res<JointGraph>,
+    sensors: Query<(), With<Sensor>>,
     mut groundeds: Query<&mut Grounded>,
     mut sounds: EventWriter<Sounds>,
+    mut events: EventWriter<CreatureEvent>,
 ) {
-    'outer: for CollisionStarted(e1, e2) in collision_event_reader.read() {
-        if creatures.contains(*e1) {
-            if creatures.contains(*e2) {
-                for joint in joints.iter() {
-                    if joint.entity1 == *e1 && joint.entity2 == *e2 {
-                        continue 'outer;
-                    }
-                    if joint.entity1 == *e2 && joint.entity2 == *e1 {
-                        continue 'outer;
-                    }
-                }
-                let (t1, c1, o1) = creatures.get(*e1).unwrap();
-                let (t2, c2, o2) = creatures.get(*e2).unwrap();
-                commands.spawn(
-                    FixedJoint::new(*e1, *e2)
-                        .with_compliance(0.00001)
-                        .with_local_anchor_1(
-                            t1.transform_point(t2.translation()).truncate().normalize()
-                                * c1.radius(),
-                        )
-                        .with_local_anchor_2(
-                            t2.transform_point(t1.translation()).truncate().normalize()
-                                * c2.radius(),
-                        ),
-                );
-                if o1.is_some() && o2.is_none() {
-                    commands.entity(*e2).insert(SpreadControl {});
-                } else if o2.is_some() && o1.is_none() {
-                    commands.entity(*e1).insert(SpreadControl {});
+    for CollisionStarted(e1, e2) in collision_event_reader.read() {
+        if creatures.contains(*e1) && creatures.contains(*e2) {
+            if graph.is_connected(*e1, *e2) {
+                continue;
+            }
+            let (t1, c1, o1) = creatures.get(*e1).unwrap();
+            let (t2, c2, o2) = creatures.get(*e2).unwrap();
+            commands.spawn(
+                FixedJoint::new(*e1, *e2)
+                    .with_compliance(0.00001)
+                    .with_local_anchor_1(
+                        t1.transform_point(t2.translation()).truncate().normalize() * c1.radius(),
+                    )
+                    .with_local_anchor_2(
+                        t2.transform_point(t1.translation()).truncate().normalize() * c2.radius(),
+                    ),
+            );
+            if o1.is_some() && o2.is_none() {
+                commands.entity(*e2).insert(SpreadControl {});
+            } else if o2.is_some() && o1.is_none() {
+                commands.entity(*e1).insert(SpreadControl {});
+            }
+            sounds.send(Sounds::Hello);
+            sounds.send(Sounds::Hello);
+            events.send(CreatureEvent::Joined(*e1, *e2));
+        } else if let Some((creature, other)) = [(e1, e2), (e2, e1)]
+            .into_iter()
+            .find(|(c, _)| creatures.contains(**c))
+        {
+            let (transform, species, controlled) = creatures.get(*creature).unwrap();
+            if let Ok((prop_transform, key)) = grabbables.get(*other) {
+                // Held props are carried, not stood on, keys are picked up by anyone
+                let grabbing = if key {
+                    !graph.is_attached(*other)
+                } else {
+                    controlled.is_some() && !graph.same_group(*creature, *other)
+                };
+                if grabbing {
+                    let dir = (prop_transform.translation() - transform.translation())
+                        .truncate()
+                        .normalize_or_zero();
+                    let hand = transform.translation().truncate() + dir * species.radius();
+                    commands.spawn(
+                        FixedJoint::new(*creature, *other)
+                            .with_compliance(0.00001)
+                            .with_local_anchor_1(
+                                transform
+                                    .affine()
+                                    .inverse()
+                                    .transform_vector3(dir.extend(0.0))
+                                    .truncate()
+                                    * species.radius(),
+                            )
+                            .with_local_anchor_2(
+                                prop_transform
+                                    .affine()
+                                    .inverse()
+                                    .transform_point3(hand.extend(0.0))
+                                    .truncate(),
+                            ),
+                    );
+                    sounds.send(Sounds::Click);
+                    continue;
                 }
-                sounds.send(Sounds::Hello);
-                sounds.send(Sounds::Hello);
-            } else {
-                groundeds
-                    .get_mut(*e1)
-                    .expect("Creatures should have `Grounded`")
-                    .0 += 1;
             }
-        } else if creatures.contains(*e2) {
+            // Water, wind, portals and plates can be touched, but not stood on
+            if sensors.contains(*other) {
+                continue;
+            }
             groundeds
-                .get_mut(*e2)
+                .get_mut(*creature)
                 .expect("Creatures should have `Grounded`")
-                .0 += 1;
+                .0
+                .insert(*other);
         }
     }
 }
@@ -354,20 +534,24 @@ fn on_collision_exit(
                 groundeds
                     .get_mut(*e1)
                     .expect("Creatures should have `Grounded`")
-                    .0 -= 1;
+                    .0
+                    .remove(e2);
             }
         } else if creatures.contains(*e2) {
             groundeds
                 .get_mut(*e2)
                 .expect("Creatures should have `Grounded`")
-                .0 -= 1;
+                .0
+                .remove(e1);
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn arms(
     joints: Query<&FixedJoint>,
     transforms: Query<(&GlobalTransform, &Creature)>,
+    props: Query<&GlobalTransform, Or<(With<Prop>, With<Key>)>>,
     mut gizmos: Gizmos<ArmGizmos>,
 ) {
     for joint in joints.iter() {
@@ -383,16 +567,24 @@ fn arms(
                     c1.color(),
                     c2.color(),
                 );
+            } else if let Ok(gt2) = props.get(joint.entity2) {
+                // Reach out to where the prop was grabbed
+                let v1 = gt1.translation().xy();
+                let v2 = gt2.transform_point(joint.local_anchor2.extend(0.0)).xy();
+                let dir = (v2 - v1).normalize_or_zero();
+                gizmos.line_2d(v1 + dir * (c1.radius() * 0.75), v2, c1.color());
             }
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn switch_main(
     mut commands: Commands,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     creatures: Query<(Entity, Option<&MainCreature>, Option<&Controlled>), With<Creature>>,
     mouths: Query<(Entity, &Parent), With<Mouth>>,
+    mut events: EventWriter<CreatureEvent>,
 ) {
     if keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::Tab]) {
         let mut skip = true;
@@ -427,6 +619,7 @@ fn switch_main(
             commands
                 .entity(e)
                 .insert((MainCreature {}, SpreadControl {}));
+            events.send(CreatureEvent::Switched(e));
         }
         for (e, p) in mouths.iter() {
             commands.entity(p.get()).remove_children(&[e]);
@@ -437,27 +630,107 @@ fn switch_main(
 
 fn on_spread_control(
     mut commands: Commands,
-    spread: Query<(Entity, &Creature), Added<SpreadControl>>,
-    joints: Query<&FixedJoint>,
-    controls: Query<(), (With<Controlled>, With<Creature>)>,
+    spread: Query<Entity, Added<SpreadControl>>,
+    creatures: Query<(&Creature, Has<Controlled>)>,
+    graph: Res<JointGraph>,
     assets: Res<CreatureAssets>,
 ) {
-    for (entity, creature) in spread.iter() {
-        commands
-            .entity(entity)
-            .remove::<SpreadControl>()
-            .insert(Controlled {})
-            .with_children(|cb| {
-                cb.spawn(mouth(creature.radius(), &assets));
-            });
-        for joint in joints.iter() {
-            if joint.entity1 == entity {
-                if !controls.contains(joint.entity2) {
-                    commands.entity(joint.entity2).insert(SpreadControl {});
+    let mut done = HashSet::new();
+    for entity in spread.iter() {
+        commands.entity(entity).remove::<SpreadControl>();
+        for e in graph.group_of(entity) {
+            if let Ok((creature, controlled)) = creatures.get(e) {
+                if !controlled && done.insert(e) {
+                    commands
+                        .entity(e)
+                        .insert(Controlled {})
+                        .with_children(|cb| {
+                            cb.spawn(mouth(creature.radius(), &assets));
+                        });
                 }
-            } else if joint.entity2 == entity && !controls.contains(joint.entity1) {
-                commands.entity(joint.entity1).insert(SpreadControl {});
             }
         }
     }
 }
+
+/// A poofed creature leaves its group, so the control is passed to the rest of the group.
+fn on_poofed(
+    mut commands: Commands,
+    mut events: EventReader<CreatureEvent>,
+    creatures: Query<(Entity, Has<MainCreature>, Has<Controlled>), With<Creature>>,
+    mouths: Query<(Entity, &Parent), With<Mouth>>,
+) {
+    let poofed: HashSet<Entity> = events
+        .read()
+        .filter_map(|event| match event {
+            CreatureEvent::Poofed(e) => Some(*e),
+            _ => None,
+        })
+        .collect();
+    if poofed.is_empty() {
+        return;
+    }
+    let mut main = None;
+    for e in poofed.iter() {
+        if let Ok((_, is_main, controlled)) = creatures.get(*e) {
+            if controlled {
+                commands.entity(*e).remove::<(Controlled, MainCreature)>();
+            }
+            if is_main {
+                main = Some(*e);
+            }
+        }
+    }
+    for (e, p) in mouths.iter() {
+        if poofed.contains(&p.get()) {
+            commands.entity(p.get()).remove_children(&[e]);
+            commands.entity(e).despawn();
+        }
+    }
+    if let Some(main) = main {
+        let next = creatures
+            .iter()
+            .find(|(e, _, controlled)| *controlled && !poofed.contains(e))
+            .map(|(e, _, _)| e);
+        match next {
+            Some(e) => {
+                commands.entity(e).insert(MainCreature {});
+            }
+            // Nobody is left in the group, so keep playing with the poofed creature
+            None => {
+                commands
+                    .entity(main)
+                    .insert((MainCreature {}, SpreadControl {}));
+            }
+        }
+    }
+}
+
+/// The control is given back to the main creature of the checkpoint, and spread through its group.
+fn on_restored(
+    mut commands: Commands,
+    mut events: EventReader<CreatureEvent>,
+    creatures: Query<Entity, With<Creature>>,
+    mouths: Query<(Entity, &Parent), With<Mouth>>,
+) {
+    let Some(main) = events
+        .read()
+        .filter_map(|event| match event {
+            CreatureEvent::Restored(e) => Some(*e),
+            _ => None,
+        })
+        .last()
+    else {
+        return;
+    };
+    for e in creatures.iter() {
+        commands.entity(e).remove::<(Controlled, MainCreature)>();
+    }
+    for (e, p) in mouths.iter() {
+        commands.entity(p.get()).remove_children(&[e]);
+        commands.entity(e).despawn();
+    }
+    commands
+        .entity(main)
+        .insert((MainCreature {}, SpreadControl {}));
+}
@@ -0,0 +1,260 @@
+use avian2d::prelude::*;
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_turborand::prelude::*;
+
+use crate::creature::{Creature, CreatureEvent};
+
+const EYE_RADIUS: f32 = 5.0;
+const PUPIL_RADIUS: f32 = 2.5;
+const BLINK_DURATION: f32 = 0.15;
+const BLINK_INTERVAL_MIN: f32 = 2.0;
+const BLINK_INTERVAL_RANGE: f32 = 3.0;
+const MOOD_DURATION: f32 = 1.0;
+const SCARED_SPEED: f32 = 450.0;
+const LOOK_SPEED: f32 = 30.0;
+
+pub struct FacePlugin;
+
+impl Plugin for FacePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Startup, setup).add_systems(
+            Update,
+            (spawn_face, (update_mood, blink, look, expression).chain()),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+enum Mood {
+    #[default]
+    Neutral,
+    Happy,
+    Scared,
+    Tongue,
+}
+
+#[derive(Resource)]
+struct FaceAssets {
+    eye_mesh: Handle<Mesh>,
+    eye_material: Handle<ColorMaterial>,
+    pupil_mesh: Handle<Mesh>,
+    pupil_material: Handle<ColorMaterial>,
+    tongue_sprite: Handle<Image>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(FaceAssets {
+        eye_mesh: meshes.add(Circle::new(EYE_RADIUS)),
+        eye_material: materials.add(Color::WHITE),
+        pupil_mesh: meshes.add(Circle::new(PUPIL_RADIUS)),
+        pupil_material: materials.add(Color::BLACK),
+        tongue_sprite: asset_server.load("sprites/tongue.png"),
+    });
+}
+
+#[derive(Component)]
+struct Face {
+    mood: Mood,
+    mood_timer: Timer,
+    blink_timer: Timer,
+    blinking: bool,
+}
+
+#[derive(Component, Clone, Copy)]
+struct Eye {}
+
+#[derive(Component, Clone, Copy)]
+struct Pupil {}
+
+#[derive(Component, Clone, Copy)]
+struct Tongue {}
+
+fn spawn_face(
+    mut commands: Commands,
+    creatures: Query<(Entity, &Creature), Added<Creature>>,
+    assets: Res<FaceAssets>,
+    mut rng: ResMut<GlobalRng>,
+) {
+    for (entity, species) in creatures.iter() {
+        let radius = species.radius();
+        commands
+            .entity(entity)
+            .insert(Face {
+                mood: Mood::Neutral,
+                mood_timer: Timer::from_seconds(MOOD_DURATION, TimerMode::Once),
+                blink_timer: Timer::from_seconds(
+                    rng.f32() * BLINK_INTERVAL_RANGE + BLINK_INTERVAL_MIN,
+                    TimerMode::Once,
+                ),
+                blinking: false,
+            })
+            .with_children(|cb| {
+                for side in [1.0, -1.0] {
+                    cb.spawn((
+                        MaterialMesh2dBundle {
+                            mesh: assets.eye_mesh.clone().into(),
+                            material: assets.eye_material.clone(),
+                            transform: Transform::from_xyz(side * radius * 0.4, radius * 0.4, 1.1),
+                            ..default()
+                        },
+                        Eye {},
+                    ))
+                    .with_children(|cb| {
+                        cb.spawn((
+                            MaterialMesh2dBundle {
+                                mesh: assets.pupil_mesh.clone().into(),
+                                material: assets.pupil_material.clone(),
+                                transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                                ..default()
+                            },
+                            Pupil {},
+                        ));
+                    });
+                }
+                cb.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::splat(radius * 0.5)),
+                            ..default()
+                        },
+                        texture: assets.tongue_sprite.clone(),
+                        transform: Transform::from_xyz(0.0, -radius * 0.55, 1.04),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    Tongue {},
+                ));
+            });
+    }
+}
+
+impl Face {
+    fn set_mood(&mut self, mood: Mood) {
+        self.mood = mood;
+        self.mood_timer.reset();
+    }
+}
+
+fn update_mood(
+    time: Res<Time>,
+    mut events: EventReader<CreatureEvent>,
+    mut faces: Query<(&mut Face, &LinearVelocity)>,
+) {
+    for event in events.read() {
+        match event {
+            CreatureEvent::Joined(e1, e2) => {
+                for e in [e1, e2] {
+                    if let Ok((mut face, _)) = faces.get_mut(*e) {
+                        face.set_mood(Mood::Happy);
+                    }
+                }
+            }
+            CreatureEvent::Jumped(e) => {
+                if let Ok((mut face, _)) = faces.get_mut(*e) {
+                    face.set_mood(Mood::Tongue);
+                }
+            }
+            CreatureEvent::Restored(e) | CreatureEvent::Switched(e) => {
+                if let Ok((mut face, _)) = faces.get_mut(*e) {
+                    face.set_mood(Mood::Happy);
+                }
+            }
+            CreatureEvent::Shoved(e) | CreatureEvent::Poofed(e) => {
+                if let Ok((mut face, _)) = faces.get_mut(*e) {
+                    face.set_mood(Mood::Scared);
+                }
+            }
+        }
+    }
+    for (mut face, velocity) in faces.iter_mut() {
+        if velocity.y < -SCARED_SPEED {
+            face.set_mood(Mood::Scared);
+        } else if face.mood != Mood::Neutral && face.mood_timer.tick(time.delta()).finished() {
+            face.mood = Mood::Neutral;
+        }
+    }
+}
+
+fn blink(time: Res<Time>, mut faces: Query<&mut Face>, mut rng: ResMut<GlobalRng>) {
+    for mut face in faces.iter_mut() {
+        if face.blink_timer.tick(time.delta()).finished() {
+            face.blinking = !face.blinking;
+            let duration = if face.blinking {
+                BLINK_DURATION
+            } else {
+                rng.f32() * BLINK_INTERVAL_RANGE + BLINK_INTERVAL_MIN
+            };
+            face.blink_timer = Timer::from_seconds(duration, TimerMode::Once);
+        }
+    }
+}
+
+fn look(
+    faces: Query<(Entity, &GlobalTransform, &LinearVelocity, &Children), With<Face>>,
+    creatures: Query<(Entity, &GlobalTransform), With<Creature>>,
+    eyes: Query<&Children, With<Eye>>,
+    mut pupils: Query<&mut Transform, With<Pupil>>,
+) {
+    for (entity, gt, velocity, children) in faces.iter() {
+        let position = gt.translation().xy();
+        let target = if velocity.length() > LOOK_SPEED {
+            velocity.0
+        } else {
+            creatures
+                .iter()
+                .filter(|(e, _)| *e != entity)
+                .map(|(_, t)| t.translation().xy() - position)
+                .min_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+                .unwrap_or(Vec2::ZERO)
+        };
+        let local = gt.compute_transform().rotation.inverse() * target.extend(0.0);
+        let offset = local.xy().normalize_or_zero() * (EYE_RADIUS - PUPIL_RADIUS);
+        for eye in eyes.iter_many(children) {
+            let mut iter = pupils.iter_many_mut(eye);
+            while let Some(mut transform) = iter.fetch_next() {
+                transform.translation.x = offset.x;
+                transform.translation.y = offset.y;
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn expression(
+    faces: Query<(&Face, &Children)>,
+    mut eyes: Query<(&mut Transform, &Children), (With<Eye>, Without<Pupil>)>,
+    mut pupils: Query<&mut Transform, (With<Pupil>, Without<Eye>)>,
+    mut tongues: Query<&mut Visibility, With<Tongue>>,
+) {
+    for (face, children) in faces.iter() {
+        let (eye_scale, pupil_scale) = if face.blinking {
+            (Vec2::new(1.0, 0.1), 1.0)
+        } else {
+            match face.mood {
+                Mood::Neutral | Mood::Tongue => (Vec2::ONE, 1.0),
+                Mood::Happy => (Vec2::new(1.1, 0.6), 1.2),
+                Mood::Scared => (Vec2::splat(1.3), 0.6),
+            }
+        };
+        for child in children.iter() {
+            if let Ok((mut transform, eye_children)) = eyes.get_mut(*child) {
+                transform.scale = eye_scale.extend(1.0);
+                let mut iter = pupils.iter_many_mut(eye_children);
+                while let Some(mut transform) = iter.fetch_next() {
+                    transform.scale = Vec3::new(pupil_scale, pupil_scale, 1.0);
+                }
+            } else if let Ok(mut visibility) = tongues.get_mut(*child) {
+                *visibility = if face.mood == Mood::Tongue {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                };
+            }
+        }
+    }
+}
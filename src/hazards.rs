@@ -0,0 +1,189 @@
+use std::f32::consts::FRAC_PI_4;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::audio::Sounds;
+use crate::creature::{Creature, CreatureEvent, SpawnPoint};
+use crate::joints::JointGraph;
+use crate::particles::ParticleEffect;
+use crate::platforms::{platform, PathMode, PlatformTrigger};
+use crate::results::playing;
+use crate::ui::Signal;
+use crate::utils::StateLocalSpawner;
+
+const SPIKE_SIZE: f32 = 16.0;
+const CRUSHER_EDGE: f32 = 6.0;
+const PIT_HEIGHT: f32 = 100.0;
+
+const SPIKE_COLOR: Color = Color::srgb(0.75, 0.75, 0.8);
+const CRUSHER_EDGE_COLOR: Color = Color::srgb(0.8, 0.2, 0.2);
+
+pub struct HazardPlugin;
+
+impl Plugin for HazardPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Update, on_hazard_collision.run_if(playing));
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HazardEffect {
+    /// Send the creature back to where it was spawned.
+    Poof,
+    /// Restart the whole level.
+    Restart,
+}
+
+#[derive(Component, Clone, Copy)]
+pub struct Hazard(pub HazardEffect);
+
+/// A row of spikes along the surface at `center`, pointing along the rotated y-axis.
+pub fn spawn_spikes(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    center: Vec2,
+    width: f32,
+    rotation: f32,
+    effect: HazardEffect,
+) {
+    let count = (width / SPIKE_SIZE).floor().max(1.0) as usize;
+    let step = width / count as f32;
+    commands
+        .spawn((
+            SpatialBundle::from_transform(
+                Transform::from_translation(center.extend(-1.1))
+                    .with_rotation(Quat::from_rotation_z(rotation)),
+            ),
+            RigidBody::Static,
+            Collider::rectangle(width, SPIKE_SIZE * 0.5),
+            Sensor,
+            Hazard(effect),
+        ))
+        .with_children(|cb| {
+            // Squares rotated by 45 degrees, half hidden behind the surface
+            for i in 0..count {
+                cb.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: SPIKE_COLOR,
+                        custom_size: Some(Vec2::splat(step * FRAC_PI_4.cos())),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz((i as f32 + 0.5) * step - width * 0.5, 0.0, 0.0)
+                        .with_rotation(Quat::from_rotation_z(FRAC_PI_4)),
+                    ..default()
+                });
+            }
+        });
+}
+
+/// An invisible hazard below the level, between `left` and `right`.
+pub fn pit(left: f32, right: f32, top: f32, effect: HazardEffect) -> impl Bundle {
+    (
+        TransformBundle::from_transform(Transform::from_xyz(
+            (left + right) * 0.5,
+            top - PIT_HEIGHT * 0.5,
+            0.0,
+        )),
+        RigidBody::Static,
+        Collider::rectangle((right - left).abs(), PIT_HEIGHT),
+        Sensor,
+        Hazard(effect),
+    )
+}
+
+/// A moving block that poofs the creatures it lands on.
+pub fn spawn_crusher(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    top: Vec2,
+    bottom: Vec2,
+    size: Vec2,
+    speed: f32,
+    trigger: Option<(u16, PlatformTrigger)>,
+) {
+    commands
+        .spawn(platform(
+            &[top, bottom],
+            size,
+            speed,
+            PathMode::PingPong,
+            trigger,
+        ))
+        .with_children(|cb| {
+            let edge = CRUSHER_EDGE / size.y;
+            cb.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: CRUSHER_EDGE_COLOR,
+                        custom_size: Some(Vec2::ONE),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, -0.5 - edge * 0.5, 0.1)
+                        .with_scale(Vec3::new(1.0, edge, 1.0)),
+                    ..default()
+                },
+                Collider::rectangle(0.9, 1.0),
+                Sensor,
+                Hazard(HazardEffect::Poof),
+            ));
+        });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn on_hazard_collision(
+    mut commands: Commands,
+    mut collision_event_reader: EventReader<CollisionStarted>,
+    hazards: Query<&Hazard>,
+    mut creatures: Query<(
+        &Creature,
+        &SpawnPoint,
+        &mut Transform,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    graph: Res<JointGraph>,
+    mut signals: EventWriter<Signal>,
+    mut events: EventWriter<CreatureEvent>,
+    mut effects: EventWriter<ParticleEffect>,
+    mut sounds: EventWriter<Sounds>,
+) {
+    let mut poofed = HashSet::new();
+    for CollisionStarted(e1, e2) in collision_event_reader.read() {
+        for (hazard, creature) in [(e1, e2), (e2, e1)] {
+            let Ok(Hazard(effect)) = hazards.get(*hazard) else {
+                continue;
+            };
+            let Ok((species, spawn, mut transform, mut velocity, mut angular)) =
+                creatures.get_mut(*creature)
+            else {
+                continue;
+            };
+            match effect {
+                HazardEffect::Restart => {
+                    signals.send(Signal::RestartLevel);
+                }
+                HazardEffect::Poof => {
+                    if !poofed.insert(*creature) {
+                        continue;
+                    }
+                    for (joint, e1, e2) in graph.joints() {
+                        if e1 == *creature || e2 == *creature {
+                            commands.entity(joint).despawn_recursive();
+                        }
+                    }
+                    effects.send(ParticleEffect::Burst(
+                        transform.translation.xy(),
+                        species.color(),
+                    ));
+                    effects.send(ParticleEffect::Burst(spawn.0, species.color()));
+                    transform.translation = spawn.0.extend(transform.translation.z);
+                    transform.rotation = Quat::IDENTITY;
+                    velocity.0 = Vec2::ZERO;
+                    angular.0 = 0.0;
+                    sounds.send(Sounds::Grunt);
+                    events.send(CreatureEvent::Poofed(*creature));
+                }
+            }
+        }
+    }
+}
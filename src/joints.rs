@@ -0,0 +1,148 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+pub struct JointPlugin;
+
+impl Plugin for JointPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.init_resource::<JointGraph>().add_systems(
+            PreUpdate,
+            (remove_dangling_joints, update_joint_graph).chain(),
+        );
+    }
+}
+
+/// Adjacency of all `FixedJoint`s, kept up to date at the start of every frame.
+#[derive(Resource, Default)]
+pub struct JointGraph {
+    joints: HashMap<Entity, (Entity, Entity)>,
+    edges: HashMap<Entity, Vec<(Entity, Entity)>>,
+    groups: HashMap<Entity, u32>,
+}
+
+impl JointGraph {
+    fn insert(&mut self, joint: Entity, e1: Entity, e2: Entity) {
+        if self.joints.insert(joint, (e1, e2)).is_none() {
+            self.edges.entry(e1).or_default().push((joint, e2));
+            self.edges.entry(e2).or_default().push((joint, e1));
+        }
+    }
+
+    fn remove(&mut self, joint: Entity) {
+        if let Some((e1, e2)) = self.joints.remove(&joint) {
+            for e in [e1, e2] {
+                if let Some(edges) = self.edges.get_mut(&e) {
+                    edges.retain(|(j, _)| *j != joint);
+                    if edges.is_empty() {
+                        self.edges.remove(&e);
+                    }
+                }
+            }
+        }
+    }
+
+    fn rebuild_groups(&mut self) {
+        self.groups.clear();
+        let mut next = 0;
+        let mut stack = Vec::new();
+        for start in self.edges.keys() {
+            if self.groups.contains_key(start) {
+                continue;
+            }
+            stack.push(*start);
+            while let Some(e) = stack.pop() {
+                if self.groups.insert(e, next).is_some() {
+                    continue;
+                }
+                stack.extend(self.neighbours(e).filter(|n| !self.groups.contains_key(n)));
+            }
+            next += 1;
+        }
+    }
+
+    /// All joints as `(joint, entity1, entity2)`.
+    pub fn joints(&self) -> impl Iterator<Item = (Entity, Entity, Entity)> + '_ {
+        self.joints.iter().map(|(j, (e1, e2))| (*j, *e1, *e2))
+    }
+
+    pub fn neighbours(&self, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.edges
+            .get(&entity)
+            .into_iter()
+            .flat_map(|edges| edges.iter().map(|(_, n)| *n))
+    }
+
+    pub fn is_attached(&self, entity: Entity) -> bool {
+        self.edges.contains_key(&entity)
+    }
+
+    pub fn is_connected(&self, e1: Entity, e2: Entity) -> bool {
+        self.neighbours(e1).any(|n| n == e2)
+    }
+
+    pub fn same_group(&self, e1: Entity, e2: Entity) -> bool {
+        self.group_id(e1).is_some() && self.group_id(e1) == self.group_id(e2)
+    }
+
+    /// The connected component id, `None` for entities without joints.
+    pub fn group_id(&self, entity: Entity) -> Option<u32> {
+        self.groups.get(&entity).copied()
+    }
+
+    /// Every entity connected to `entity` (including itself) through any number of joints.
+    pub fn group_of(&self, entity: Entity) -> Vec<Entity> {
+        match self.group_id(entity) {
+            Some(id) => self
+                .groups
+                .iter()
+                .filter(|(_, g)| **g == id)
+                .map(|(e, _)| *e)
+                .collect(),
+            None => vec![entity],
+        }
+    }
+
+    /// Total mass of the group, entities without a `Mass` (e.g. static anchors) are ignored.
+    pub fn group_mass(&self, entity: Entity, masses: &Query<&Mass>) -> f32 {
+        self.group_of(entity)
+            .into_iter()
+            .filter_map(|e| masses.get(e).ok())
+            .map(|m| m.0)
+            .sum()
+    }
+}
+
+/// Despawns the joints of bodies that have been despawned.
+fn remove_dangling_joints(
+    mut commands: Commands,
+    graph: Res<JointGraph>,
+    mut removed: RemovedComponents<RigidBody>,
+) {
+    for entity in removed.read() {
+        for (joint, _) in graph.edges.get(&entity).into_iter().flatten() {
+            if let Some(ec) = commands.get_entity(*joint) {
+                ec.despawn_recursive();
+            }
+        }
+    }
+}
+
+fn update_joint_graph(
+    mut graph: ResMut<JointGraph>,
+    added: Query<(Entity, &FixedJoint), Added<FixedJoint>>,
+    mut removed: RemovedComponents<FixedJoint>,
+) {
+    let mut changed = false;
+    for joint in removed.read() {
+        graph.remove(joint);
+        changed = true;
+    }
+    for (entity, joint) in added.iter() {
+        graph.insert(entity, joint.entity1, joint.entity2);
+        changed = true;
+    }
+    if changed {
+        graph.rebuild_groups();
+    }
+}
@@ -0,0 +1,113 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::creature::{Creature, CreatureBody, CreatureEvent, Grounded};
+use crate::ui::Settings;
+
+const LAND_SPEED: f32 = 600.0;
+const MAX_SQUASH: f32 = 0.3;
+const SQUASH_RECOVERY: f32 = 8.0;
+const STRETCH_SPEED: f32 = 1200.0;
+const MAX_STRETCH: f32 = 0.15;
+const WOBBLE_DURATION: f32 = 0.5;
+const WOBBLE_AMPLITUDE: f32 = 0.12;
+const WOBBLE_FREQUENCY: f32 = 30.0;
+
+pub struct JuicePlugin;
+
+impl Plugin for JuicePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Update, (add_squash, (on_creature_event, squash).chain()));
+    }
+}
+
+#[derive(Component, Clone, Copy, Default)]
+struct Squash {
+    grounded: bool,
+    velocity: Vec2,
+    impact: f32,
+    wobble: f32,
+}
+
+fn add_squash(mut commands: Commands, creatures: Query<Entity, Added<Creature>>) {
+    for entity in creatures.iter() {
+        commands.entity(entity).insert(Squash::default());
+    }
+}
+
+fn on_creature_event(mut events: EventReader<CreatureEvent>, mut squashes: Query<&mut Squash>) {
+    for event in events.read() {
+        if let CreatureEvent::Joined(e1, e2) = event {
+            for e in [e1, e2] {
+                if let Ok(mut squash) = squashes.get_mut(*e) {
+                    squash.wobble = 1.0;
+                }
+            }
+        }
+    }
+}
+
+fn squash(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut creatures: Query<
+        (
+            &Transform,
+            &LinearVelocity,
+            &Grounded,
+            &mut Squash,
+            &Children,
+        ),
+        Without<CreatureBody>,
+    >,
+    mut bodies: Query<&mut Transform, With<CreatureBody>>,
+) {
+    let delta_time = time.delta_seconds();
+    for (transform, velocity, grounded, mut squash, children) in creatures.iter_mut() {
+        let grounded = grounded.is_grounded();
+        if grounded && !squash.grounded {
+            let impact = (-squash.velocity.y / LAND_SPEED).clamp(0.0, 1.0);
+            squash.impact = squash.impact.max(impact);
+        }
+        squash.grounded = grounded;
+        squash.velocity = velocity.0;
+        squash.impact = (squash.impact - squash.impact * SQUASH_RECOVERY * delta_time).max(0.0);
+        squash.wobble = (squash.wobble - delta_time / WOBBLE_DURATION).max(0.0);
+
+        // The scale is applied along `axis`, which is then rotated back to
+        // counter the rolling of the parent.
+        let (axis, mut along, mut across) = if squash.impact > 0.01 {
+            let amount = squash.impact * MAX_SQUASH;
+            (Vec2::Y, 1.0 - amount, 1.0 + amount)
+        } else if !grounded {
+            let amount = (velocity.length() / STRETCH_SPEED).min(1.0) * MAX_STRETCH;
+            (
+                velocity.normalize_or(Vec2::Y),
+                1.0 + amount,
+                1.0 - amount * 0.5,
+            )
+        } else {
+            (Vec2::Y, 1.0, 1.0)
+        };
+        if squash.wobble > 0.0 {
+            let amount = (time.elapsed_seconds() * WOBBLE_FREQUENCY).sin()
+                * squash.wobble
+                * WOBBLE_AMPLITUDE;
+            along *= 1.0 + amount;
+            across *= 1.0 - amount;
+        }
+        let (rotation, scale) = if settings.reduced_motion {
+            (Quat::IDENTITY, Vec3::ONE)
+        } else {
+            (
+                transform.rotation.inverse() * Quat::from_rotation_z(axis.to_angle()),
+                Vec3::new(along, across, 1.0),
+            )
+        };
+        let mut iter = bodies.iter_many_mut(children);
+        while let Some(mut body) = iter.fetch_next() {
+            body.rotation = rotation;
+            body.scale = scale;
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::audio::Sounds;
+use crate::joints::JointGraph;
+use crate::objects::{door, DoorMode, PressurePlateEvent};
+use crate::particles::ParticleEffect;
+use crate::ui::Signal;
+use crate::utils::StateLocalSpawner;
+
+const KEY_SIZE: Vec2 = Vec2::new(30.0, 12.0);
+const KEYHOLE_SIZE: Vec2 = Vec2::new(8.0, 20.0);
+
+const KEY_COLORS: [Color; 3] = [
+    Color::srgb(1.0, 0.8, 0.2),
+    Color::srgb(0.3, 0.8, 0.9),
+    Color::srgb(0.9, 0.4, 0.8),
+];
+
+pub struct KeyPlugin;
+
+impl Plugin for KeyPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Update, unlock);
+    }
+}
+
+/// Attaches to any creature touching it, and is dropped by shoving.
+#[derive(Component, Clone, Copy)]
+pub struct Key(pub u16);
+
+/// Opens the door when touched by the key with the same number, or by the group carrying it.
+/// Plates sending the same `Signal::Door` do not open locked doors.
+#[derive(Component, Clone, Copy)]
+pub struct Lock(u16);
+
+fn key_color(number: u16) -> Color {
+    KEY_COLORS[number as usize % KEY_COLORS.len()]
+}
+
+pub fn spawn_key(commands: &mut StateLocalSpawner<'_, '_>, number: u16, position: Vec2) -> Entity {
+    let color = key_color(number);
+    commands
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_translation(position.extend(0.6))),
+            RigidBody::Dynamic,
+            Collider::rectangle(KEY_SIZE.x, KEY_SIZE.y),
+            ColliderDensity(0.5),
+            Key(number),
+        ))
+        .with_children(|cb| {
+            // The bow
+            cb.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(KEY_SIZE.y)),
+                    ..default()
+                },
+                transform: Transform::from_xyz((KEY_SIZE.y - KEY_SIZE.x) * 0.5, 0.0, 0.0),
+                ..default()
+            });
+            // The shaft
+            cb.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::new(KEY_SIZE.x, KEY_SIZE.y * 0.3)),
+                    ..default()
+                },
+                ..default()
+            });
+            // The bit
+            cb.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::new(KEY_SIZE.y * 0.3, KEY_SIZE.y * 0.5)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(KEY_SIZE.x * 0.35, -KEY_SIZE.y * 0.25, 0.0),
+                ..default()
+            });
+        })
+        .id()
+}
+
+/// A latched `door` that is opened by a key instead of a plate.
+pub fn spawn_locked_door(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    number: u16,
+    bottom: Vec2,
+    height: f32,
+) {
+    let bundle = door(number, DoorMode::Latched, bottom, height);
+    commands.spawn((bundle, Lock(number))).with_children(|cb| {
+        // The door is scaled, so the size is relative
+        cb.spawn(SpriteBundle {
+            sprite: Sprite {
+                color: key_color(number),
+                custom_size: Some(Vec2::ONE),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, 0.1).with_scale(Vec3::new(
+                0.6,
+                KEYHOLE_SIZE.y / height,
+                1.0,
+            )),
+            ..default()
+        });
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn unlock(
+    mut commands: Commands,
+    mut collision_event_reader: EventReader<CollisionStarted>,
+    locks: Query<(&Lock, &GlobalTransform)>,
+    keys: Query<&Key>,
+    graph: Res<JointGraph>,
+    mut plate_events: EventWriter<PressurePlateEvent>,
+    mut sounds: EventWriter<Sounds>,
+    mut effects: EventWriter<ParticleEffect>,
+) {
+    // `Lock` is only removed once the commands are applied, so a lock can be touched again
+    let mut unlocked = HashSet::new();
+    let mut used = HashSet::new();
+    for CollisionStarted(e1, e2) in collision_event_reader.read() {
+        for (lock_entity, other) in [(e1, e2), (e2, e1)] {
+            let Ok((Lock(number), transform)) = locks.get(*lock_entity) else {
+                continue;
+            };
+            if unlocked.contains(lock_entity) {
+                continue;
+            }
+            let Some(key) = graph
+                .group_of(*other)
+                .into_iter()
+                .find(|e| !used.contains(e) && keys.get(*e).is_ok_and(|k| k.0 == *number))
+            else {
+                continue;
+            };
+            unlocked.insert(*lock_entity);
+            used.insert(key);
+            commands.entity(key).despawn_recursive();
+            commands
+                .entity(*lock_entity)
+                .remove::<Lock>()
+                .despawn_descendants();
+            plate_events.send(PressurePlateEvent(
+                *lock_entity,
+                Signal::Door(*number),
+                true,
+            ));
+            sounds.send(Sounds::Click);
+            effects.send(ParticleEffect::Burst(
+                transform.translation().xy(),
+                key_color(*number),
+            ));
+        }
+    }
+}
@@ -1,10 +1,25 @@
+use crate::breakables::breakable;
+use crate::checkpoints::spawn_checkpoint;
+use crate::collectibles::{spawn_collectible, Collectible};
+use crate::contraptions::{spawn_rope_bridge, spawn_seesaw, spawn_trapdoor};
 use crate::creature::{Creature, CreatureAssets};
+use crate::hazards::{pit, spawn_crusher, spawn_spikes, HazardEffect};
+use crate::keys::{spawn_key, spawn_locked_door};
+use crate::logic::{logic, Gate};
 use crate::objects::{
-    background, camera, door, plank, spawn_exit, spawn_glass, spawn_pressure_plate, wall,
-    PressurePlateEvent,
+    background, camera, door, plank, spawn_bumper, spawn_conditional_exit, spawn_exit, spawn_glass,
+    spawn_launch_pad, spawn_pressure_plate, spawn_springboard, spawn_weight_plate, wall, DoorMode,
+    ExitCondition, ObjectAssets, PressurePlateEvent,
 };
+use crate::platforms::{one_way, platform, spawn_conveyor, PathMode, PlatformTrigger};
+use crate::portals::spawn_portals;
+use crate::props::{spawn_prop, Prop, PropAssets};
+use crate::results::LevelStats;
+use crate::save::SaveData;
 use crate::ui::{spawn_button, spawn_sign, Signal, TextStyles};
 use crate::utils::{IdentityTransitionsPlugin, StateLocalPlugin, StateLocalSpawner};
+use crate::water::water;
+use crate::wind::spawn_wind;
 use avian2d::math::PI;
 use avian2d::prelude::*;
 use bevy::prelude::*;
@@ -77,20 +92,15 @@ fn level_events(
     mut events: EventReader<PressurePlateEvent>,
     mut next_state: ResMut<NextState<Level>>,
     state: Res<State<Level>>,
+    stats: Res<LevelStats>,
 ) {
+    // Reaching the exit shows the results first, which continue with a `Signal::NextLevel`
     for PressurePlateEvent(_, signal, pressed) in events.read() {
-        match signal {
-            Signal::NextLevel => {
-                if *pressed {
-                    next_state.set(state.get().next().unwrap_or_default())
-                }
-            }
-            Signal::RestartLevel => {
-                if *pressed {
-                    next_state.set(*state.get())
-                }
+        if let (Signal::RestartLevel, true) = (signal, pressed) {
+            // Plates do not restart the level behind the results
+            if !stats.complete() {
+                next_state.set(*state.get());
             }
-            _ => {}
         }
     }
     for signal in signals.read() {
@@ -102,7 +112,12 @@ fn level_events(
     }
 }
 
-fn setup_main_menu(commands: Commands, text_style: Res<TextStyles>, assets: Res<CreatureAssets>) {
+fn setup_main_menu(
+    commands: Commands,
+    text_style: Res<TextStyles>,
+    assets: Res<CreatureAssets>,
+    save: Res<SaveData>,
+) {
     let mut commands = StateLocalSpawner(commands);
     commands.spawn(Camera2dBundle {
         projection: OrthographicProjection {
@@ -138,8 +153,18 @@ fn setup_main_menu(commands: Commands, text_style: Res<TextStyles>, assets: Res<
                 "Play",
                 Val::Px(200.0),
                 Val::Px(80.0),
-                text_style,
+                &text_style,
             );
+            let collected = save.total_collected();
+            if collected > 0 {
+                cb.spawn(
+                    TextBundle::from_section(
+                        format!("Treasures found: {collected}"),
+                        text_style.button_text.clone(),
+                    )
+                    .with_text_justify(JustifyText::Center),
+                );
+            }
         });
 
     commands.spawn(background(
@@ -175,7 +200,13 @@ fn setup_main_menu(commands: Commands, text_style: Res<TextStyles>, assets: Res<
     commands.spawn(plank(Vec2::new(-100.0, -75.0), Vec2::new(-0.0, -75.0)));
 }
 
-fn setup_test_level(commands: Commands, assets: Res<CreatureAssets>, text_styles: Res<TextStyles>) {
+fn setup_test_level(
+    commands: Commands,
+    assets: Res<CreatureAssets>,
+    object_assets: Res<ObjectAssets>,
+    prop_assets: Res<PropAssets>,
+    text_styles: Res<TextStyles>,
+) {
     let mut cmd = StateLocalSpawner(commands);
     cmd.spawn(camera());
 
@@ -188,8 +219,12 @@ fn setup_test_level(commands: Commands, assets: Res<CreatureAssets>, text_styles
     cmd.spawn(plank(Vec2::new(-300.0, 10.0), Vec2::new(-450.0, -10.0)));
     cmd.spawn(plank(Vec2::new(250.0, 10.0), Vec2::new(300.0, 10.0)));
     cmd.spawn(plank(Vec2::new(-250.0, 10.0), Vec2::new(-300.0, 10.0)));
+    cmd.spawn((
+        plank(Vec2::new(-250.0, 10.0), Vec2::new(-150.0, 10.0)),
+        breakable(1_500_000.0),
+    ));
 
-    spawn_exit(&mut cmd, Vec2::new(275.0, 10.0), 60.0, 0.0);
+    spawn_checkpoint(&mut cmd, Vec2::new(-275.0, 22.5), 40.0, 0.0);
     spawn_pressure_plate(
         &mut cmd,
         Signal::Custom(0),
@@ -200,6 +235,161 @@ fn setup_test_level(commands: Commands, assets: Res<CreatureAssets>, text_styles
 
     spawn_glass(&mut cmd, Vec2::new(250.0, -275.), 80.);
 
+    cmd.spawn(door(0, DoorMode::Timed(3.0), Vec2::new(-400., -275.), 100.));
+    spawn_pressure_plate(&mut cmd, Signal::Door(0), Vec2::new(-175., -275.), 60., 0.0);
+    cmd.spawn(door(1, DoorMode::Latched, Vec2::new(400., -275.), 100.));
+    spawn_weight_plate(
+        &mut cmd,
+        Signal::Door(1),
+        Vec2::new(175., -275.),
+        60.,
+        0.0,
+        3000.0,
+    );
+
+    spawn_pressure_plate(
+        &mut cmd,
+        Signal::Custom(1),
+        Vec2::new(-100.0, -275.0),
+        60.0,
+        0.0,
+    );
+    cmd.spawn(logic(Gate::Toggle, &[0], &[Signal::Custom(10)]));
+    cmd.spawn(logic(Gate::Pulse(3.0), &[1], &[Signal::Custom(11)]));
+    cmd.spawn(logic(Gate::And, &[10, 11], &[Signal::Custom(12)]));
+    cmd.spawn(logic(Gate::Delay(1.0), &[12], &[Signal::Custom(13)]));
+    cmd.spawn(logic(Gate::Counter(3), &[1], &[Signal::Custom(14)]));
+    cmd.spawn(logic(Gate::Or, &[13, 14], &[Signal::Custom(15)]));
+    cmd.spawn(logic(Gate::Not, &[15], &[Signal::Door(2)]));
+    cmd.spawn(door(2, DoorMode::Momentary, Vec2::new(340., -275.), 100.));
+
+    cmd.spawn(platform(
+        &[Vec2::new(0.0, -265.0), Vec2::new(0.0, -100.0)],
+        Vec2::new(80.0, 15.0),
+        60.0,
+        PathMode::Linear,
+        Some((0, PlatformTrigger::Start)),
+    ));
+    cmd.spawn(platform(
+        &[Vec2::new(-200.0, 150.0), Vec2::new(200.0, 150.0)],
+        Vec2::new(100.0, 15.0),
+        80.0,
+        PathMode::PingPong,
+        Some((1, PlatformTrigger::Reverse)),
+    ));
+    cmd.spawn(platform(
+        &[
+            Vec2::new(300.0, 200.0),
+            Vec2::new(400.0, 150.0),
+            Vec2::new(250.0, 120.0),
+        ],
+        Vec2::new(60.0, 15.0),
+        60.0,
+        PathMode::Loop,
+        Some((1, PlatformTrigger::Stop)),
+    ));
+
+    spawn_springboard(
+        &mut cmd,
+        Vec2::new(-450.0, -240.0),
+        Vec2::new(-410.0, -275.0),
+    );
+    spawn_bumper(&mut cmd, Vec2::new(0.0, 220.0), 20.0, &object_assets);
+    spawn_launch_pad(
+        &mut cmd,
+        0,
+        Vec2::new(410.0, -275.0),
+        Vec2::new(450.0, -275.0),
+        900.0,
+    );
+
+    cmd.spawn(water(Vec2::new(-250.0, -200.0), Vec2::new(-130.0, -275.0)));
+    spawn_wind(
+        &mut cmd,
+        Vec2::new(-100.0, 80.0),
+        Vec2::new(300.0, 100.0),
+        0.0,
+        700.0,
+        0.5,
+        Some(14),
+    );
+
+    spawn_spikes(
+        &mut cmd,
+        Vec2::new(150.0, 275.0),
+        100.0,
+        PI,
+        HazardEffect::Poof,
+    );
+    spawn_crusher(
+        &mut cmd,
+        Vec2::new(-375.0, 200.0),
+        Vec2::new(-375.0, 45.0),
+        Vec2::new(60.0, 30.0),
+        100.0,
+        None,
+    );
+    cmd.spawn(pit(-500.0, 500.0, -325.0, HazardEffect::Restart));
+
+    spawn_prop(
+        &mut cmd,
+        Prop::Crate,
+        Vec2::new(-350.0, 60.0),
+        Vec2::splat(40.0),
+        false,
+        &prop_assets,
+    );
+    spawn_prop(
+        &mut cmd,
+        Prop::Ball,
+        Vec2::new(350.0, 60.0),
+        Vec2::splat(30.0),
+        true,
+        &prop_assets,
+    );
+    spawn_prop(
+        &mut cmd,
+        Prop::Barrel,
+        Vec2::new(100.0, -240.0),
+        Vec2::new(35.0, 50.0),
+        true,
+        &prop_assets,
+    );
+
+    cmd.spawn((
+        plank(Vec2::new(60.0, -160.0), Vec2::new(160.0, -170.0)),
+        one_way(),
+    ));
+    spawn_collectible(&mut cmd, Collectible::Shell, Vec2::new(110.0, -130.0));
+    spawn_collectible(&mut cmd, Collectible::IceCream, Vec2::new(-200.0, 40.0));
+    spawn_collectible(&mut cmd, Collectible::Sunglasses, Vec2::new(0.0, 260.0));
+    spawn_portals(
+        &mut cmd,
+        (Vec2::new(-449.0, -200.0), -PI * 0.5),
+        (Vec2::new(200.0, 274.0), PI),
+        60.0,
+        2,
+    );
+    spawn_seesaw(&mut cmd, Vec2::new(100.0, -265.0), 160.0, 0.3);
+    spawn_key(&mut cmd, 5, Vec2::new(-60.0, -260.0));
+    spawn_conveyor(
+        &mut cmd,
+        Vec2::new(220.0, -150.0),
+        Vec2::new(380.0, -150.0),
+        15.0,
+        80.0,
+        Some(0),
+    );
+    spawn_locked_door(&mut cmd, 5, Vec2::new(430.0, -8.0), 100.0);
+    spawn_trapdoor(&mut cmd, Vec2::new(-150.0, 10.0), 100.0, 1);
+    spawn_rope_bridge(
+        &mut cmd,
+        Vec2::new(-150.0, 230.0),
+        Vec2::new(150.0, 230.0),
+        8,
+        0.5,
+    );
+
     spawn_sign(
         &mut cmd,
         "Press N to go to the next level",
@@ -209,10 +399,17 @@ fn setup_test_level(commands: Commands, assets: Res<CreatureAssets>, text_styles
     );
 
     let d = 30.0;
-    Creature::spawn(&mut cmd, -d * 3.0, 0.0, Creature::Normal, true, &assets);
+    let tony = Creature::spawn(&mut cmd, -d * 3.0, 0.0, Creature::Normal, true, &assets);
     Creature::spawn(&mut cmd, -d * 1.0, 0.0, Creature::Explosive, false, &assets);
     Creature::spawn(&mut cmd, d * 1.0, 0.0, Creature::Bouncy, false, &assets);
-    Creature::spawn(&mut cmd, d * 3.0, 0.0, Creature::Heavy, false, &assets);
+    let ricky = Creature::spawn(&mut cmd, d * 3.0, 0.0, Creature::Heavy, false, &assets);
+    spawn_conditional_exit(
+        &mut cmd,
+        Vec2::new(275.0, 10.0),
+        60.0,
+        0.0,
+        ExitCondition::Subset(vec![tony, ricky]),
+    );
 }
 
 fn setup_tutorial1(commands: Commands, assets: Res<CreatureAssets>, text_styles: Res<TextStyles>) {
@@ -349,7 +546,7 @@ fn setup_tutorial5(commands: Commands, assets: Res<CreatureAssets>, text_styles:
 
     cmd.spawn(wall(Vec2::new(175.0, 275.0), Vec2::new(225.0, -275.0)));
     cmd.spawn(wall(Vec2::new(-175.0, 275.0), Vec2::new(-225.0, -175.0)));
-    cmd.spawn(door(0, Vec2::new(-200., -275.), 100.));
+    cmd.spawn(door(0, DoorMode::Latched, Vec2::new(-200., -275.), 100.));
 
     spawn_pressure_plate(&mut cmd, Signal::Door(0), Vec2::new(350., -275.), 60., 0.0);
     spawn_exit(&mut cmd, Vec2::new(-350.0, -275.0), 60.0, 0.0);
@@ -409,8 +606,8 @@ fn setup_level2(commands: Commands, assets: Res<CreatureAssets>) {
     spawn_pressure_plate(&mut cmd, Signal::Door(1), Vec2::new(-350., 30.), 60., 0.0);
 
     cmd.spawn(wall(Vec2::new(175.0, 275.0), Vec2::new(225.0, -175.0)));
-    cmd.spawn(door(0, Vec2::new(210., -275.), 100.));
-    cmd.spawn(door(1, Vec2::new(190., -275.), 100.));
+    cmd.spawn(door(0, DoorMode::Latched, Vec2::new(210., -275.), 100.));
+    cmd.spawn(door(1, DoorMode::Latched, Vec2::new(190., -275.), 100.));
 
     spawn_exit(&mut cmd, Vec2::new(350.0, -275.0), 60.0, 0.0);
 
@@ -430,7 +627,7 @@ fn setup_level3(commands: Commands, assets: Res<CreatureAssets>) {
 
     cmd.spawn(wall(Vec2::new(-450.0, 0.0), Vec2::new(-200.0, -50.0)));
     cmd.spawn(wall(Vec2::new(-250.0, -50.0), Vec2::new(-200.0, -175.0)));
-    cmd.spawn(door(0, Vec2::new(-225., -275.), 100.));
+    cmd.spawn(door(0, DoorMode::Latched, Vec2::new(-225., -275.), 100.));
 
     cmd.spawn(wall(Vec2::new(100.0, 10.0), Vec2::new(120.0, -275.0)));
     cmd.spawn(wall(Vec2::new(200.0, 10.0), Vec2::new(220.0, -275.0)));
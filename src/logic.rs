@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+
+use crate::objects::PressurePlateEvent;
+use crate::ui::Signal;
+
+pub struct LogicPlugin;
+
+impl Plugin for LogicPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Update, update_logic);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gate {
+    /// All inputs are pressed.
+    And,
+    /// Any input is pressed.
+    Or,
+    /// No input is pressed.
+    Not,
+    /// Flips every time an input is pressed.
+    Toggle,
+    /// Follows the inputs once they have been unchanged for the given seconds.
+    Delay(f32),
+    /// Active for the given seconds after an input is pressed.
+    Pulse(f32),
+    /// Active once the inputs have been pressed the given number of times, and stays active
+    /// until the level is restarted.
+    Counter(u16),
+}
+
+/// An invisible node that listens to `Signal::Custom` channels and drives its output signals.
+/// Outputs are sent as `PressurePlateEvent`s, so every node in a chain adds a frame of latency.
+#[derive(Component, Clone)]
+pub struct LogicNode {
+    gate: Gate,
+    inputs: Vec<(u16, u16)>,
+    outputs: Vec<Signal>,
+    state: bool,
+    last_input: bool,
+    timer: Timer,
+    count: u16,
+}
+
+pub fn logic(gate: Gate, inputs: &[u16], outputs: &[Signal]) -> impl Bundle {
+    let seconds = match gate {
+        Gate::Delay(s) | Gate::Pulse(s) => s,
+        _ => 0.0,
+    };
+    // Start with a finished timer so that nothing is pending at the start of the level
+    let mut timer = Timer::from_seconds(seconds, TimerMode::Once);
+    timer.tick(Duration::from_secs_f32(seconds));
+    LogicNode {
+        gate,
+        inputs: inputs.iter().map(|i| (*i, 0)).collect(),
+        outputs: outputs.to_vec(),
+        state: false,
+        last_input: false,
+        timer,
+        count: 0,
+    }
+}
+
+impl LogicNode {
+    pub fn outputs(&self) -> impl Iterator<Item = Signal> + '_ {
+        self.outputs.iter().copied()
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.state
+    }
+
+    fn evaluate(&mut self, delta: Duration) -> bool {
+        let any = self.inputs.iter().any(|(_, c)| *c > 0);
+        let all = self.inputs.iter().all(|(_, c)| *c > 0);
+        let rising = any && !self.last_input;
+        let changed = any != self.last_input;
+        self.last_input = any;
+        match self.gate {
+            Gate::And => all,
+            Gate::Or => any,
+            Gate::Not => !any,
+            Gate::Toggle => self.state ^ rising,
+            Gate::Delay(_) => {
+                if changed {
+                    self.timer.reset();
+                }
+                if self.timer.tick(delta).finished() {
+                    any
+                } else {
+                    self.state
+                }
+            }
+            Gate::Pulse(_) => {
+                if rising {
+                    self.timer.reset();
+                    true
+                } else {
+                    !self.timer.tick(delta).finished()
+                }
+            }
+            Gate::Counter(n) => {
+                if rising {
+                    self.count += 1;
+                }
+                self.count >= n
+            }
+        }
+    }
+}
+
+fn update_logic(
+    time: Res<Time>,
+    mut events: ResMut<Events<PressurePlateEvent>>,
+    mut reader: Local<ManualEventReader<PressurePlateEvent>>,
+    mut nodes: Query<(Entity, &mut LogicNode)>,
+) {
+    let incoming: Vec<(u16, bool)> = reader
+        .read(&events)
+        .filter_map(|PressurePlateEvent(_, signal, pressed)| match signal {
+            Signal::Custom(i) => Some((*i, *pressed)),
+            _ => None,
+        })
+        .collect();
+    for (entity, mut node) in nodes.iter_mut() {
+        for (channel, pressed) in incoming.iter() {
+            for (input, count) in node.inputs.iter_mut() {
+                if input == channel {
+                    *count = if *pressed {
+                        *count + 1
+                    } else {
+                        count.saturating_sub(1)
+                    };
+                }
+            }
+        }
+        let state = node.evaluate(time.delta());
+        if state != node.state {
+            node.state = state;
+            for output in node.outputs.iter() {
+                events.send(PressurePlateEvent(entity, *output, state));
+            }
+        }
+    }
+}
@@ -1,9 +1,28 @@
 mod audio;
+mod breakables;
+mod checkpoints;
+mod collectibles;
+mod contraptions;
 mod creature;
+mod face;
+mod hazards;
+mod joints;
+mod juice;
+mod keys;
 mod levels;
+mod logic;
 mod objects;
+mod particles;
+mod platforms;
+mod portals;
+mod props;
+mod results;
+mod rewind;
+mod save;
 mod ui;
 mod utils;
+mod water;
+mod wind;
 
 use avian2d::{math::*, prelude::*};
 use bevy::prelude::*;
@@ -15,11 +34,34 @@ fn main() {
             DefaultPlugins,
             PhysicsPlugins::default().with_length_unit(40.0),
             RngPlugin::default(),
+        ))
+        .add_plugins((
             creature::CreaturePlugin,
-            objects::ObjectPlugin,
+            face::FacePlugin,
+            juice::JuicePlugin,
+            joints::JointPlugin,
+            particles::ParticlePlugin,
             ui::UiPlugin,
             audio::AudioPlugin,
+            logic::LogicPlugin,
             levels::LevelPlugin,
+            save::SavePlugin,
+            rewind::RewindPlugin,
+        ))
+        .add_plugins((
+            objects::ObjectPlugin,
+            breakables::BreakablePlugin,
+            checkpoints::CheckpointPlugin,
+            collectibles::CollectiblePlugin,
+            contraptions::ContraptionPlugin,
+            hazards::HazardPlugin,
+            keys::KeyPlugin,
+            platforms::PlatformPlugin,
+            portals::PortalPlugin,
+            props::PropPlugin,
+            results::ResultsPlugin,
+            water::WaterPlugin,
+            wind::WindPlugin,
         ))
         .insert_resource(ClearColor(Color::srgb(0.15, 0.15, 0.25)))
         .insert_resource(Gravity(Vector::NEG_Y * 9.81 * 50.0))
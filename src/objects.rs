@@ -1,9 +1,17 @@
 use avian2d::prelude::*;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+use bevy::utils::HashSet;
 
 use crate::audio::Sounds;
 use crate::creature::{Creature, MainCreature};
-use crate::ui::Signal;
+use crate::joints::JointGraph;
+use crate::keys::Lock;
+use crate::logic::LogicNode;
+use crate::particles::ParticleEffect;
+use crate::props::Prop;
+use crate::ui::{Signal, TextStyles};
 use crate::utils::StateLocalSpawner;
 
 const PLANK_THICKNESS: f32 = 25.0;
@@ -11,32 +19,74 @@ const SENSOR_THICKNESS: f32 = 6.0;
 const EXIT_HEIGHT: f32 = 70.0;
 const GLASS_THICKNESS: f32 = 8.0;
 const DOOR_THICKNESS: f32 = 12.0;
+const DOOR_SPEED: f32 = 150.0;
+const SPRING_THICKNESS: f32 = 12.0;
+const SPRINGBOARD_SPEED: f32 = 700.0;
+const BUMPER_SPEED: f32 = 500.0;
+const SPRING_RECOVERY: f32 = 6.0;
 const CAMERA_SPEED: f32 = 200.0;
+/// Teleports longer than this (like portals) move the camera directly instead of panning.
+const CAMERA_SNAP: f32 = 200.0;
 
 const STATIC_COLOR: Color = Color::srgb(0.8, 0.75, 1.0);
 const SENSOR_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
 const EXIT_COLOR: Color = Color::srgba(1.0, 1.0, 0.0, 0.7);
 const GLASS_COLOR: Color = Color::srgba(0.7, 0.75, 1.0, 0.7);
 const DOOR_COLOR: Color = Color::srgb(0.35, 0.4, 0.3);
+const PLATE_FILL_COLOR: Color = Color::srgb(1.0, 0.6, 0.2);
+const SPRING_COLOR: Color = Color::srgb(0.3, 0.8, 0.7);
+const BUMPER_COLOR: Color = Color::srgb(1.0, 0.45, 0.6);
+const LAUNCH_PAD_COLOR: Color = Color::srgb(0.95, 0.7, 0.3);
 
 pub struct ObjectPlugin;
 
 impl Plugin for ObjectPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_systems(
-            Update,
-            (
-                on_pressure_enter,
-                on_pressure_exit,
-                on_pressure_event,
-                glass_collision,
-                camera_follow,
-            ),
-        )
-        .add_event::<PressurePlateEvent>();
+        app.add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                (
+                    (
+                        on_pressure_enter,
+                        on_pressure_exit,
+                        update_pressure_plates,
+                        update_exit_hud,
+                    )
+                        .chain(),
+                    on_pressure_event,
+                    glass_collision,
+                    move_doors,
+                    (
+                        on_spring_enter,
+                        on_spring_exit,
+                        on_launch_signal,
+                        animate_springs,
+                    )
+                        .chain(),
+                    camera_follow,
+                ),
+            )
+            .add_event::<PressurePlateEvent>();
     }
 }
 
+#[derive(Resource)]
+pub struct ObjectAssets {
+    circle_mesh: Handle<Mesh>,
+    bumper_material: Handle<ColorMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    commands.insert_resource(ObjectAssets {
+        circle_mesh: meshes.add(Circle::new(1.0)),
+        bumper_material: materials.add(BUMPER_COLOR),
+    });
+}
+
 pub fn camera() -> impl Bundle {
     Camera2dBundle {
         projection: OrthographicProjection {
@@ -51,10 +101,19 @@ pub fn camera() -> impl Bundle {
 fn camera_follow(
     time: Res<Time>,
     mut camera: Query<(&mut Transform, &GlobalTransform, &Camera)>,
-    target: Query<&GlobalTransform, With<MainCreature>>,
+    target: Query<(Entity, &GlobalTransform), With<MainCreature>>,
+    mut last: Local<Option<(Entity, Vec3)>>,
 ) {
     if let Ok((mut transform, gt, camera)) = camera.get_single_mut() {
-        if let Ok(creature) = target.get_single() {
+        if let Ok((entity, creature)) = target.get_single() {
+            let previous = last.replace((entity, creature.translation()));
+            if previous.is_some_and(|(e, p)| {
+                e == entity && p.distance(creature.translation()) > CAMERA_SNAP
+            }) {
+                transform.translation.x = creature.translation().x;
+                transform.translation.y = creature.translation().y;
+                return;
+            }
             if let Some(ndc) = camera.world_to_ndc(gt, creature.translation()) {
                 if ndc.x < -0.4 {
                     transform.translation.x -= time.delta_seconds() * CAMERA_SPEED;
@@ -71,12 +130,16 @@ fn camera_follow(
     }
 }
 
-pub fn plank(start: Vec2, end: Vec2) -> impl Bundle {
-    let angle = if end.x < start.x {
+fn plank_angle(start: Vec2, end: Vec2) -> f32 {
+    if end.x < start.x {
         f32::atan2(start.y - end.y, start.x - end.x)
     } else {
         f32::atan2(end.y - start.y, end.x - start.x)
-    };
+    }
+}
+
+pub fn plank(start: Vec2, end: Vec2) -> impl Bundle {
+    let angle = plank_angle(start, end);
     let offset = (Quat::from_rotation_z(angle) * Vec3::new(0.0, PLANK_THICKNESS * 0.5, 0.0)).xy();
     rectangle(
         start.midpoint(end) - offset,
@@ -111,6 +174,226 @@ pub fn rectangle(center: Vec2, size: Vec2, rotation: f32) -> impl Bundle {
     )
 }
 
+/// Launches creatures away, either along `normal` or radially from the center.
+#[derive(Component, Clone)]
+pub struct Spring {
+    normal: Option<Vec2>,
+    speed: f32,
+    trigger: Option<u16>,
+    bodies: HashSet<Entity>,
+    compression: f32,
+}
+
+/// The resting scale of the visual.
+#[derive(Component, Clone, Copy)]
+struct SpringVisual(Vec3);
+
+fn spawn_spring_surface(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    start: Vec2,
+    end: Vec2,
+    color: Color,
+    speed: f32,
+    trigger: Option<u16>,
+) -> Entity {
+    let angle = plank_angle(start, end);
+    let normal = Vec2::from_angle(angle).perp();
+    commands
+        .spawn((
+            TransformBundle {
+                local: Transform::from_translation(
+                    (start.midpoint(end) - normal * SPRING_THICKNESS * 0.5).extend(-0.5),
+                )
+                .with_scale(Vec3::new(start.distance(end), SPRING_THICKNESS, 1.0))
+                .with_rotation(Quat::from_rotation_z(angle)),
+                ..default()
+            },
+            VisibilityBundle::default(),
+            RigidBody::Static,
+            Collider::rectangle(1.0, 1.0),
+            Spring {
+                normal: Some(normal),
+                speed,
+                trigger,
+                bodies: HashSet::new(),
+                compression: 0.0,
+            },
+        ))
+        .with_children(|cb| {
+            cb.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(Vec2::ONE),
+                        ..default()
+                    },
+                    ..default()
+                },
+                SpringVisual(Vec3::ONE),
+            ));
+        })
+        .id()
+}
+
+pub fn spawn_springboard(commands: &mut StateLocalSpawner<'_, '_>, start: Vec2, end: Vec2) {
+    spawn_spring_surface(commands, start, end, SPRING_COLOR, SPRINGBOARD_SPEED, None);
+}
+
+/// A springboard that only launches when the `Signal::Custom` channel is pressed.
+pub fn spawn_launch_pad(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    channel: u16,
+    start: Vec2,
+    end: Vec2,
+    speed: f32,
+) {
+    spawn_spring_surface(commands, start, end, LAUNCH_PAD_COLOR, speed, Some(channel));
+}
+
+pub fn spawn_bumper(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    center: Vec2,
+    radius: f32,
+    assets: &Res<ObjectAssets>,
+) {
+    commands
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_translation(center.extend(-0.5))),
+            RigidBody::Static,
+            Collider::circle(radius),
+            Spring {
+                normal: None,
+                speed: BUMPER_SPEED,
+                trigger: None,
+                bodies: HashSet::new(),
+                compression: 0.0,
+            },
+        ))
+        .with_children(|cb| {
+            cb.spawn((
+                MaterialMesh2dBundle {
+                    mesh: assets.circle_mesh.clone().into(),
+                    material: assets.bumper_material.clone(),
+                    transform: Transform::from_scale(Vec3::new(radius, radius, 1.0)),
+                    ..default()
+                },
+                SpringVisual(Vec3::new(radius, radius, 1.0)),
+            ));
+        });
+}
+
+impl Spring {
+    fn launch(
+        &mut self,
+        center: Vec2,
+        position: Vec2,
+        velocity: &mut LinearVelocity,
+        creature: Creature,
+    ) {
+        let direction = self
+            .normal
+            .unwrap_or_else(|| (position - center).normalize_or_zero());
+        // Replace the velocity towards the spring instead of adding to it
+        let speed = self.speed * (0.5 + creature.bounciness());
+        velocity.0 += direction * (speed - velocity.dot(direction));
+        self.compression = 1.0;
+    }
+}
+
+fn on_spring_enter(
+    mut collision_event_reader: EventReader<CollisionStarted>,
+    mut creatures: Query<(&mut LinearVelocity, &GlobalTransform, &Creature)>,
+    mut springs: Query<(&mut Spring, &GlobalTransform), Without<Creature>>,
+    mut sounds: EventWriter<Sounds>,
+) {
+    for CollisionStarted(e1, e2) in collision_event_reader.read() {
+        for (creature, spring) in [(e1, e2), (e2, e1)] {
+            if let Ok((mut spring, spring_transform)) = springs.get_mut(*spring) {
+                if let Ok((mut velocity, transform, species)) = creatures.get_mut(*creature) {
+                    if spring.trigger.is_some() {
+                        spring.bodies.insert(*creature);
+                    } else {
+                        spring.launch(
+                            spring_transform.translation().xy(),
+                            transform.translation().xy(),
+                            &mut velocity,
+                            *species,
+                        );
+                        sounds.send(Sounds::Click);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn on_spring_exit(
+    mut collision_event_reader: EventReader<CollisionEnded>,
+    mut springs: Query<&mut Spring>,
+) {
+    for CollisionEnded(e1, e2) in collision_event_reader.read() {
+        for (creature, spring) in [(e1, e2), (e2, e1)] {
+            if let Ok(mut spring) = springs.get_mut(*spring) {
+                spring.bodies.remove(creature);
+            }
+        }
+    }
+}
+
+fn on_launch_signal(
+    mut events: EventReader<PressurePlateEvent>,
+    mut creatures: Query<(&mut LinearVelocity, &GlobalTransform, &Creature)>,
+    mut springs: Query<(&mut Spring, &GlobalTransform), Without<Creature>>,
+    mut sounds: EventWriter<Sounds>,
+) {
+    for PressurePlateEvent(_, signal, pressed) in events.read() {
+        let (Signal::Custom(i), true) = (signal, pressed) else {
+            continue;
+        };
+        for (mut spring, spring_transform) in springs.iter_mut() {
+            if spring.trigger != Some(*i) {
+                continue;
+            }
+            for body in spring.bodies.clone() {
+                if let Ok((mut velocity, transform, species)) = creatures.get_mut(body) {
+                    spring.launch(
+                        spring_transform.translation().xy(),
+                        transform.translation().xy(),
+                        &mut velocity,
+                        *species,
+                    );
+                }
+            }
+            spring.compression = 1.0;
+            sounds.send(Sounds::Click);
+        }
+    }
+}
+
+fn animate_springs(
+    time: Res<Time>,
+    mut springs: Query<(&mut Spring, &Children)>,
+    mut visuals: Query<(&mut Transform, &SpringVisual)>,
+) {
+    for (mut spring, children) in springs.iter_mut() {
+        if spring.compression <= 0.0 {
+            continue;
+        }
+        spring.compression = (spring.compression - time.delta_seconds() * SPRING_RECOVERY).max(0.0);
+        let mut iter = visuals.iter_many_mut(children);
+        while let Some((mut transform, visual)) = iter.fetch_next() {
+            if spring.normal.is_some() {
+                // Squeeze the surface down towards its base
+                transform.scale.y = visual.0.y * (1.0 - spring.compression * 0.5);
+                transform.translation.y = -spring.compression * 0.25;
+            } else {
+                transform.scale = visual.0 * (1.0 + spring.compression * 0.2);
+                transform.scale.z = 1.0;
+            }
+        }
+    }
+}
+
 #[derive(Component, Clone, Copy)]
 pub struct Glass {}
 
@@ -144,30 +427,26 @@ pub fn spawn_glass(commands: &mut StateLocalSpawner<'_, '_>, bottom: Vec2, heigh
 fn glass_collision(
     mut collision_event_reader: EventReader<CollisionStarted>,
     creatures: Query<(), With<Creature>>,
-    mut glasses: Query<&mut RigidBody, With<Glass>>,
-    joints: Query<&FixedJoint>,
+    mut glasses: Query<(&mut RigidBody, &GlobalTransform), With<Glass>>,
+    graph: Res<JointGraph>,
     mut sounds: EventWriter<Sounds>,
+    mut effects: EventWriter<ParticleEffect>,
 ) {
-    'outer: for CollisionStarted(e1, e2) in collision_event_reader.read() {
-        if creatures.contains(*e1) {
-            if let Ok(mut rb) = glasses.get_mut(*e2) {
-                for joint in joints.iter() {
-                    if joint.entity1 == *e1 || joint.entity2 == *e1 {
-                        *rb = RigidBody::Dynamic;
-                        sounds.send(Sounds::Glass);
-                        continue 'outer;
-                    }
-                }
-            }
+    for CollisionStarted(e1, e2) in collision_event_reader.read() {
+        let (creature, glass) = if creatures.contains(*e1) {
+            (*e1, *e2)
         } else if creatures.contains(*e2) {
-            if let Ok(mut rb) = glasses.get_mut(*e1) {
-                for joint in joints.iter() {
-                    if joint.entity1 == *e2 || joint.entity2 == *e2 {
-                        *rb = RigidBody::Dynamic;
-                        sounds.send(Sounds::Glass);
-                        continue 'outer;
-                    }
-                }
+            (*e2, *e1)
+        } else {
+            continue;
+        };
+        if let Ok((mut rb, transform)) = glasses.get_mut(glass) {
+            // Only other creatures count, carrying a prop is not enough
+            let attached = graph.neighbours(creature).any(|n| creatures.contains(n));
+            if attached && rb.is_static() {
+                *rb = RigidBody::Dynamic;
+                sounds.send(Sounds::Glass);
+                effects.send(ParticleEffect::Shards(transform.translation().xy()));
             }
         }
     }
@@ -193,10 +472,92 @@ pub fn background(
     }
 }
 
-#[derive(Component, Clone, Copy)]
-pub struct Door(u16);
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DoorMode {
+    /// Open only while a plate is pressed.
+    Momentary,
+    /// Stays open once opened.
+    Latched,
+    /// Closes the given number of seconds after the plate is released.
+    Timed(f32),
+}
+
+#[derive(Component, Clone)]
+pub struct Door {
+    number: u16,
+    mode: DoorMode,
+    pressed: u16,
+    open: bool,
+    timer: Timer,
+    closed_y: f32,
+}
+
+impl Door {
+    fn press(&mut self, pressed: bool) {
+        if pressed {
+            self.pressed += 1;
+            self.open = true;
+        } else {
+            self.pressed = self.pressed.saturating_sub(1);
+            if self.pressed == 0 {
+                match self.mode {
+                    DoorMode::Momentary => self.open = false,
+                    DoorMode::Latched => {}
+                    DoorMode::Timed(_) => self.timer.reset(),
+                }
+            }
+        }
+    }
+
+    /// Returns to an earlier state, but counts the plates that are pressed now.
+    pub fn restore(&mut self, earlier: &Door, sources: &DoorSources) {
+        if let Some(pressed) = sources.pressing(self.number) {
+            self.pressed = pressed;
+        }
+        self.open = match self.mode {
+            DoorMode::Momentary => self.pressed > 0,
+            _ => earlier.open || self.pressed > 0,
+        };
+        self.timer = earlier.timer.clone();
+    }
+}
 
-pub fn door(number: u16, bottom: Vec2, height: f32) -> impl Bundle {
+/// The plates and logic nodes that can hold doors open.
+#[derive(SystemParam)]
+pub struct DoorSources<'w, 's> {
+    plates: Query<'w, 's, &'static PressurePlate>,
+    nodes: Query<'w, 's, &'static LogicNode>,
+}
+
+impl DoorSources<'_, '_> {
+    /// How many of them press the door right now, `None` for doors opened by something else.
+    fn pressing(&self, number: u16) -> Option<u16> {
+        let is_door = |signal: &Signal| matches!(signal, Signal::Door(n) if *n == number);
+        let sources: Vec<bool> = self
+            .plates
+            .iter()
+            .filter(|p| is_door(&p.signal))
+            .map(|p| p.pressed)
+            .chain(
+                self.nodes
+                    .iter()
+                    .filter(|n| n.outputs().any(|s| is_door(&s)))
+                    .map(|n| n.is_on()),
+            )
+            .collect();
+        if sources.is_empty() {
+            return None;
+        }
+        Some(sources.iter().filter(|on| **on).count() as u16)
+    }
+}
+
+pub fn door(number: u16, mode: DoorMode, bottom: Vec2, height: f32) -> impl Bundle {
+    let delay = if let DoorMode::Timed(seconds) = mode {
+        seconds
+    } else {
+        0.0
+    };
     (
         SpriteBundle {
             sprite: Sprite {
@@ -204,22 +565,89 @@ pub fn door(number: u16, bottom: Vec2, height: f32) -> impl Bundle {
                 custom_size: Some(Vec2::ONE),
                 ..default()
             },
+            // Behind the walls, so that the door slides up into them.
             transform: Transform::from_translation(Vec3::new(
                 bottom.x,
                 bottom.y + height * 0.5,
-                0.0,
+                -1.5,
             ))
             .with_scale(Vec3::new(DOOR_THICKNESS, height, 1.0)),
             ..default()
         },
         RigidBody::Kinematic,
         Collider::rectangle(1.0, 1.0),
-        Door(number),
+        Door {
+            number,
+            mode,
+            pressed: 0,
+            open: false,
+            timer: Timer::from_seconds(delay, TimerMode::Once),
+            closed_y: bottom.y + height * 0.5,
+        },
     )
 }
 
+fn move_doors(
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    mut doors: Query<(Entity, &mut Door, &Transform, &mut LinearVelocity)>,
+    bodies: Query<&RigidBody>,
+) {
+    let delta_time = time.delta_seconds();
+    for (entity, mut door, transform, mut velocity) in doors.iter_mut() {
+        if let DoorMode::Timed(_) = door.mode {
+            if door.pressed == 0 && door.open && door.timer.tick(time.delta()).just_finished() {
+                door.open = false;
+            }
+        }
+        let height = transform.scale.y;
+        let target = if door.open {
+            door.closed_y + height
+        } else {
+            door.closed_y
+        };
+        let diff = target - transform.translation.y;
+        if !door.open && diff < 0.0 {
+            // Do not crush anything that is in the way of the closing door
+            let gap = -diff;
+            let center = Vec2::new(
+                transform.translation.x,
+                transform.translation.y - (height + gap) * 0.5,
+            );
+            let blocked = spatial_query
+                .shape_intersections(
+                    &Collider::rectangle(DOOR_THICKNESS, gap),
+                    center,
+                    0.0,
+                    SpatialQueryFilter::default().with_excluded_entities([entity]),
+                )
+                .into_iter()
+                .any(|e| bodies.get(e).is_ok_and(|rb| rb.is_dynamic()));
+            if blocked {
+                velocity.y = 0.0;
+                continue;
+            }
+        }
+        velocity.y = if delta_time > 0.0 {
+            (diff / delta_time).clamp(-DOOR_SPEED, DOOR_SPEED)
+        } else {
+            0.0
+        };
+    }
+}
+
+/// Pressed by any creature, or by a total mass of creature groups and
+/// dynamic objects if `required_mass` is positive.
+#[derive(Component, Clone)]
+pub struct PressurePlate {
+    signal: Signal,
+    required_mass: f32,
+    bodies: HashSet<Entity>,
+    pressed: bool,
+}
+
 #[derive(Component, Clone, Copy)]
-pub struct PressurePlate(u16, Signal);
+struct PlateFill {}
 
 #[derive(Event, Debug, Clone, Copy)]
 pub struct PressurePlateEvent(pub Entity, pub Signal, pub bool);
@@ -230,6 +658,17 @@ pub fn spawn_pressure_plate(
     center: Vec2,
     width: f32,
     rotation: f32,
+) -> Entity {
+    spawn_plate(commands, signal, center, width, rotation, 0.0)
+}
+
+fn spawn_plate(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    signal: Signal,
+    center: Vec2,
+    width: f32,
+    rotation: f32,
+    required_mass: f32,
 ) -> Entity {
     commands
         .spawn((
@@ -247,7 +686,12 @@ pub fn spawn_pressure_plate(
             RigidBody::Static,
             Collider::rectangle(1.0, 1.0),
             Sensor,
-            PressurePlate(0, signal),
+            PressurePlate {
+                signal,
+                required_mass,
+                bodies: HashSet::new(),
+                pressed: false,
+            },
         ))
         .with_children(|cb| {
             cb.spawn((SpriteBundle {
@@ -263,13 +707,55 @@ pub fn spawn_pressure_plate(
         .id()
 }
 
+/// Which creatures have to reach the exit before the level is completed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExitCondition {
+    /// Anything pressing the plate, like any other plate.
+    Any,
+    /// Every one of the given creatures.
+    Subset(Vec<Entity>),
+}
+
+/// Creatures are at the beach when they, or anyone in their group, touch the exit.
+#[derive(Component, Clone)]
+pub struct Exit {
+    condition: ExitCondition,
+    at_beach: usize,
+    needed: usize,
+}
+
+#[derive(Component, Clone, Copy)]
+struct ExitHud {}
+
 pub fn spawn_exit(
     commands: &mut StateLocalSpawner<'_, '_>,
     center: Vec2,
     width: f32,
     rotation: f32,
 ) {
+    spawn_conditional_exit(commands, center, width, rotation, ExitCondition::Any);
+}
+
+pub fn spawn_conditional_exit(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    center: Vec2,
+    width: f32,
+    rotation: f32,
+    condition: ExitCondition,
+) {
+    let condition = match condition {
+        ExitCondition::Subset(subset) if subset.is_empty() => {
+            warn!("An exit for an empty subset is opened by anyone");
+            ExitCondition::Any
+        }
+        condition => condition,
+    };
     let e = spawn_pressure_plate(commands, Signal::NextLevel, center, width, rotation);
+    commands.entity(e).insert(Exit {
+        condition,
+        at_beach: 0,
+        needed: 0,
+    });
     commands.entity(e).with_children(|cb| {
         cb.spawn((SpriteBundle {
             sprite: Sprite {
@@ -284,25 +770,43 @@ pub fn spawn_exit(
     });
 }
 
+pub fn spawn_weight_plate(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    signal: Signal,
+    center: Vec2,
+    width: f32,
+    rotation: f32,
+    required_mass: f32,
+) -> Entity {
+    let e = spawn_plate(commands, signal, center, width, rotation, required_mass);
+    commands.entity(e).with_children(|cb| {
+        cb.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: PLATE_FILL_COLOR,
+                    custom_size: Some(Vec2::ONE),
+                    ..default()
+                },
+                transform: Transform::from_xyz(-0.5, 0.0, 0.05)
+                    .with_scale(Vec3::new(0.0, 1.0, 1.0)),
+                ..default()
+            },
+            PlateFill {},
+        ));
+    });
+    e
+}
+
 fn on_pressure_enter(
     mut collision_event_reader: EventReader<CollisionStarted>,
-    creatures: Query<(), With<Creature>>,
+    bodies: Query<&RigidBody>,
     mut plates: Query<&mut PressurePlate>,
-    mut event: EventWriter<PressurePlateEvent>,
 ) {
     for CollisionStarted(e1, e2) in collision_event_reader.read() {
-        if creatures.contains(*e1) {
-            if let Ok(mut plate) = plates.get_mut(*e2) {
-                plate.0 += 1;
-                if plate.0 == 1 {
-                    event.send(PressurePlateEvent(*e2, plate.1, true));
-                }
-            }
-        } else if creatures.contains(*e2) {
-            if let Ok(mut plate) = plates.get_mut(*e1) {
-                plate.0 += 1;
-                if plate.0 == 1 {
-                    event.send(PressurePlateEvent(*e1, plate.1, true));
+        for (body, plate) in [(e1, e2), (e2, e1)] {
+            if bodies.get(*body).is_ok_and(|rb| rb.is_dynamic()) {
+                if let Ok(mut plate) = plates.get_mut(*plate) {
+                    plate.bodies.insert(*body);
                 }
             }
         }
@@ -311,56 +815,135 @@ fn on_pressure_enter(
 
 fn on_pressure_exit(
     mut collision_event_reader: EventReader<CollisionEnded>,
-    creatures: Query<(), With<Creature>>,
     mut plates: Query<&mut PressurePlate>,
-    mut event: EventWriter<PressurePlateEvent>,
 ) {
     for CollisionEnded(e1, e2) in collision_event_reader.read() {
-        if creatures.contains(*e1) {
-            if let Ok(mut plate) = plates.get_mut(*e2) {
-                plate.0 -= 1;
-                if plate.0 == 0 {
-                    event.send(PressurePlateEvent(*e2, plate.1, false));
-                }
+        for (body, plate) in [(e1, e2), (e2, e1)] {
+            if let Ok(mut plate) = plates.get_mut(*plate) {
+                plate.bodies.remove(body);
             }
-        } else if creatures.contains(*e2) {
-            if let Ok(mut plate) = plates.get_mut(*e1) {
-                plate.0 -= 1;
-                if plate.0 == 0 {
-                    event.send(PressurePlateEvent(*e1, plate.1, false));
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_pressure_plates(
+    mut plates: Query<(Entity, &mut PressurePlate, &Children, Option<&mut Exit>)>,
+    pressers: Query<(), Or<(With<Creature>, With<Prop>)>>,
+    creatures: Query<(Entity, &Creature)>,
+    masses: Query<&Mass>,
+    graph: Res<JointGraph>,
+    mut fills: Query<&mut Transform, With<PlateFill>>,
+    mut event: EventWriter<PressurePlateEvent>,
+) {
+    for (entity, mut plate, children, exit) in plates.iter_mut() {
+        plate.bodies.retain(|e| masses.contains(*e));
+        let exit = exit.filter(|exit| exit.condition != ExitCondition::Any);
+        let pressed = if let Some(mut exit) = exit {
+            let at_beach: HashSet<Entity> = plate
+                .bodies
+                .iter()
+                .flat_map(|body| graph.group_of(*body))
+                .filter(|e| creatures.contains(*e))
+                .collect();
+            let (count, needed) = match &exit.condition {
+                ExitCondition::Subset(subset) => (
+                    subset.iter().filter(|e| at_beach.contains(*e)).count(),
+                    subset.len(),
+                ),
+                ExitCondition::Any => unreachable!("Exits for anyone are plain plates"),
+            };
+            if exit.at_beach != count || exit.needed != needed {
+                exit.at_beach = count;
+                exit.needed = needed;
+            }
+            // Nobody left to bring along does not complete the level by itself
+            needed > 0 && !plate.bodies.is_empty() && count >= needed
+        } else if plate.required_mass > 0.0 {
+            let mut counted = HashSet::new();
+            let mut mass = 0.0;
+            for body in plate.bodies.iter() {
+                // Count every group once, even when several of its bodies are on the plate
+                if counted.insert(graph.group_id(*body).ok_or(*body)) {
+                    mass += graph.group_mass(*body, &masses);
                 }
             }
+            let progress = (mass / plate.required_mass).min(1.0);
+            let mut iter = fills.iter_many_mut(children);
+            while let Some(mut transform) = iter.fetch_next() {
+                transform.scale.x = progress;
+                transform.translation.x = (progress - 1.0) * 0.5;
+            }
+            mass >= plate.required_mass
+        } else {
+            plate.bodies.iter().any(|e| pressers.contains(*e))
+        };
+        if pressed != plate.pressed {
+            plate.pressed = pressed;
+            event.send(PressurePlateEvent(entity, plate.signal, pressed));
         }
     }
 }
 
+fn update_exit_hud(
+    commands: Commands,
+    exits: Query<&Exit, Changed<Exit>>,
+    mut huds: Query<&mut Text, With<ExitHud>>,
+    text_styles: Res<TextStyles>,
+) {
+    let Some(exit) = exits.iter().find(|e| e.condition != ExitCondition::Any) else {
+        return;
+    };
+    let text = format!("{}/{} friends at the beach", exit.at_beach, exit.needed);
+    if let Ok(mut hud) = huds.get_single_mut() {
+        hud.sections[0].value = text;
+    } else {
+        StateLocalSpawner(commands).spawn((
+            TextBundle::from_section(text, text_styles.sign_text.clone()).with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                ..default()
+            }),
+            ExitHud {},
+        ));
+    }
+}
+
 fn on_pressure_event(
-    mut commands: Commands,
     mut event: EventReader<PressurePlateEvent>,
     children: Query<&Children>,
     mut transforms: Query<&mut Transform>,
-    mut doors: Query<(Entity, &Door, &mut Visibility)>,
+    mut doors: Query<(&mut Door, Has<Lock>)>,
+    plates: Query<&GlobalTransform, With<PressurePlate>>,
     mut sounds: EventWriter<Sounds>,
+    mut effects: EventWriter<ParticleEffect>,
 ) {
     for PressurePlateEvent(entity, signal, pressed) in event.read() {
+        if let (Signal::NextLevel, true) = (signal, pressed) {
+            if let Ok(transform) = plates.get(*entity) {
+                effects.send(ParticleEffect::Confetti(transform.translation().xy()));
+            }
+        }
         for child in children.iter_descendants(*entity) {
             if let Ok(mut t) = transforms.get_mut(child) {
                 t.translation.y = if *pressed { -0.75 } else { 0.0 };
             }
-            if let Signal::Door(i) = signal {
-                for (e, door, mut v) in doors.iter_mut() {
-                    if door.0 == *i {
-                        if *pressed {
-                            commands.entity(e).insert(Sensor {});
-                            *v = Visibility::Hidden;
-                        } else {
-                            // commands.entity(e).remove::<Sensor>();
-                            // *v = Visibility::Inherited;
-                        }
+        }
+        if let Signal::Door(i) = signal {
+            if let Ok((mut door, _)) = doors.get_mut(*entity) {
+                // Sent by the door itself when it is unlocked
+                door.press(*pressed);
+            } else {
+                for (mut door, locked) in doors.iter_mut() {
+                    if door.number == *i && !locked {
+                        door.press(*pressed);
                     }
                 }
             }
         }
-        sounds.send(Sounds::Click);
+        if plates.contains(*entity) {
+            sounds.send(Sounds::Click);
+        }
     }
 }
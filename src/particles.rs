@@ -0,0 +1,215 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+
+use crate::utils::StateLocalSpawner;
+
+const DUST_COLOR: Color = Color::srgba(0.85, 0.85, 0.8, 0.8);
+const SHARD_COLOR: Color = Color::srgba(0.7, 0.75, 1.0, 0.9);
+const SPLASH_COLOR: Color = Color::srgba(0.6, 0.8, 1.0, 0.8);
+const SPLINTER_COLOR: Color = Color::srgb(0.6, 0.55, 0.75);
+const CONFETTI_COLORS: [Color; 5] = [
+    Color::srgb(1.0, 0.3, 0.3),
+    Color::srgb(1.0, 0.9, 0.2),
+    Color::srgb(0.3, 0.9, 0.4),
+    Color::srgb(0.3, 0.6, 1.0),
+    Color::srgb(0.9, 0.4, 1.0),
+];
+const GRAVITY: f32 = -600.0;
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Update, (spawn_particles, update_particles))
+            .add_event::<ParticleEffect>();
+    }
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub enum ParticleEffect {
+    /// Dust puff below a jumping creature.
+    Dust(Vec2),
+    /// Radial burst from a shoved creature.
+    Burst(Vec2, Color),
+    /// Shards from a breaking glass pane.
+    Shards(Vec2),
+    /// Celebration when reaching the exit.
+    Confetti(Vec2),
+    /// Droplets from a creature crossing a water surface.
+    Splash(Vec2),
+    /// Pieces from a breaking plank or wall.
+    Splinters(Vec2),
+}
+
+#[derive(Component, Clone, Copy)]
+struct Particle {
+    velocity: Vec2,
+    gravity: f32,
+    drag: f32,
+    spin: f32,
+    age: f32,
+    lifetime: f32,
+    alpha: f32,
+}
+
+struct Emission {
+    count: usize,
+    color: Color,
+    size: Vec2,
+    speed: (f32, f32),
+    angle: (f32, f32),
+    lifetime: f32,
+    gravity: f32,
+    drag: f32,
+    spin: f32,
+}
+
+impl ParticleEffect {
+    fn position(self) -> Vec2 {
+        match self {
+            ParticleEffect::Dust(p)
+            | ParticleEffect::Burst(p, _)
+            | ParticleEffect::Shards(p)
+            | ParticleEffect::Confetti(p)
+            | ParticleEffect::Splash(p)
+            | ParticleEffect::Splinters(p) => p,
+        }
+    }
+
+    fn emission(self) -> Emission {
+        match self {
+            ParticleEffect::Dust(_) => Emission {
+                count: 8,
+                color: DUST_COLOR,
+                size: Vec2::splat(6.0),
+                speed: (40.0, 90.0),
+                angle: (0.0, PI),
+                lifetime: 0.4,
+                gravity: 50.0,
+                drag: 3.0,
+                spin: 0.0,
+            },
+            ParticleEffect::Burst(_, color) => Emission {
+                count: 16,
+                color,
+                size: Vec2::splat(5.0),
+                speed: (200.0, 300.0),
+                angle: (0.0, TAU),
+                lifetime: 0.5,
+                gravity: 0.0,
+                drag: 4.0,
+                spin: 0.0,
+            },
+            ParticleEffect::Shards(_) => Emission {
+                count: 12,
+                color: SHARD_COLOR,
+                size: Vec2::new(3.0, 9.0),
+                speed: (100.0, 250.0),
+                angle: (0.0, PI),
+                lifetime: 1.0,
+                gravity: GRAVITY,
+                drag: 0.5,
+                spin: 10.0,
+            },
+            ParticleEffect::Confetti(_) => Emission {
+                count: 40,
+                color: CONFETTI_COLORS[0],
+                size: Vec2::new(5.0, 8.0),
+                speed: (300.0, 500.0),
+                angle: (PI * 0.3, PI * 0.7),
+                lifetime: 1.5,
+                gravity: GRAVITY,
+                drag: 1.0,
+                spin: 8.0,
+            },
+            ParticleEffect::Splash(_) => Emission {
+                count: 14,
+                color: SPLASH_COLOR,
+                size: Vec2::splat(5.0),
+                speed: (150.0, 300.0),
+                angle: (PI * 0.25, PI * 0.75),
+                lifetime: 0.6,
+                gravity: GRAVITY,
+                drag: 1.0,
+                spin: 0.0,
+            },
+            ParticleEffect::Splinters(_) => Emission {
+                count: 6,
+                color: SPLINTER_COLOR,
+                size: Vec2::new(3.0, 8.0),
+                speed: (80.0, 200.0),
+                angle: (0.0, PI),
+                lifetime: 0.8,
+                gravity: GRAVITY,
+                drag: 0.5,
+                spin: 12.0,
+            },
+        }
+    }
+}
+
+fn spawn_particles(
+    commands: Commands,
+    mut effects: EventReader<ParticleEffect>,
+    mut rng: ResMut<GlobalRng>,
+) {
+    let mut cmd = StateLocalSpawner(commands);
+    for effect in effects.read() {
+        let emission = effect.emission();
+        let position = effect.position();
+        for i in 0..emission.count {
+            let angle = emission.angle.0 + rng.f32() * (emission.angle.1 - emission.angle.0);
+            let speed = emission.speed.0 + rng.f32() * (emission.speed.1 - emission.speed.0);
+            let color = if let ParticleEffect::Confetti(_) = effect {
+                CONFETTI_COLORS[i % CONFETTI_COLORS.len()]
+            } else {
+                emission.color
+            };
+            cmd.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(emission.size),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(position.extend(2.0))
+                        .with_rotation(Quat::from_rotation_z(rng.f32() * TAU)),
+                    ..default()
+                },
+                Particle {
+                    velocity: Vec2::from_angle(angle) * speed,
+                    gravity: emission.gravity,
+                    drag: emission.drag,
+                    spin: (rng.f32() * 2.0 - 1.0) * emission.spin,
+                    age: 0.0,
+                    lifetime: emission.lifetime * (rng.f32() * 0.5 + 0.75),
+                    alpha: color.alpha(),
+                },
+            ));
+        }
+    }
+}
+
+fn update_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particles: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    let delta_time = time.delta_seconds();
+    for (entity, mut particle, mut transform, mut sprite) in particles.iter_mut() {
+        particle.age += delta_time;
+        if particle.age >= particle.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let drag = (1.0 - particle.drag * delta_time).max(0.0);
+        particle.velocity.y += particle.gravity * delta_time;
+        particle.velocity *= drag;
+        transform.translation += (particle.velocity * delta_time).extend(0.0);
+        transform.rotate_z(particle.spin * delta_time);
+        let alpha = particle.alpha * (1.0 - particle.age / particle.lifetime);
+        sprite.color.set_alpha(alpha);
+    }
+}
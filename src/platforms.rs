@@ -0,0 +1,409 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::objects::PressurePlateEvent;
+use crate::ui::Signal;
+use crate::utils::StateLocalSpawner;
+
+const STRIPE_SPACING: f32 = 20.0;
+/// Acceleration of the bodies resting on a conveyor, until they move as fast as its surface.
+const CONVEYOR_GRIP: f32 = 1000.0;
+
+const PLATFORM_COLOR: Color = Color::srgb(0.65, 0.6, 0.9);
+const CONVEYOR_COLOR: Color = Color::srgb(0.35, 0.35, 0.4);
+const STRIPE_COLOR: Color = Color::srgb(0.55, 0.55, 0.6);
+
+pub struct PlatformPlugin;
+
+impl Plugin for PlatformPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(
+            Update,
+            (
+                (platform_signals, move_platforms).chain(),
+                (conveyor_signals, animate_stripes).chain(),
+            ),
+        )
+        .add_systems(PostProcessCollisions, one_way_platforms)
+        .add_systems(
+            PhysicsSchedule,
+            carry_on_conveyors.in_set(PhysicsStepSet::First),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathMode {
+    /// Move to the last waypoint and stop there.
+    Linear,
+    /// Move back and forth between the first and the last waypoint.
+    PingPong,
+    /// Continue from the last waypoint to the first.
+    Loop,
+}
+
+/// How a platform reacts while its `Signal::Custom` channel is pressed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlatformTrigger {
+    Start,
+    Stop,
+    Reverse,
+}
+
+#[derive(Component, Clone)]
+pub struct Platform {
+    waypoints: Vec<Vec2>,
+    speed: f32,
+    path: PathMode,
+    trigger: Option<(u16, PlatformTrigger)>,
+    pressed: u16,
+    from: usize,
+    next: usize,
+    forward: bool,
+    reversed: bool,
+}
+
+pub fn platform(
+    waypoints: &[Vec2],
+    size: Vec2,
+    speed: f32,
+    path: PathMode,
+    trigger: Option<(u16, PlatformTrigger)>,
+) -> impl Bundle {
+    let start = waypoints.first().copied().unwrap_or_default();
+    (
+        SpriteBundle {
+            sprite: Sprite {
+                color: PLATFORM_COLOR,
+                custom_size: Some(Vec2::ONE),
+                ..default()
+            },
+            transform: Transform::from_translation(start.extend(-1.0)).with_scale(size.extend(1.0)),
+            ..default()
+        },
+        RigidBody::Kinematic,
+        Collider::rectangle(1.0, 1.0),
+        Friction::new(1.0),
+        Platform {
+            waypoints: waypoints.to_vec(),
+            speed,
+            path,
+            trigger,
+            pressed: 0,
+            from: 0,
+            next: usize::from(waypoints.len() > 1),
+            forward: true,
+            reversed: false,
+        },
+    )
+}
+
+impl Platform {
+    fn running(&self) -> bool {
+        match self.trigger {
+            Some((_, PlatformTrigger::Start)) => self.pressed > 0,
+            Some((_, PlatformTrigger::Stop)) => self.pressed == 0,
+            _ => true,
+        }
+    }
+
+    fn direction(&self) -> bool {
+        self.forward ^ self.reversed
+    }
+
+    fn set_reversed(&mut self, reversed: bool) {
+        if self.reversed != reversed {
+            self.reversed = reversed;
+            std::mem::swap(&mut self.from, &mut self.next);
+        }
+    }
+
+    /// The waypoint after `next`, or `None` if the platform should stop.
+    fn step(&mut self) -> Option<usize> {
+        let last = self.waypoints.len() - 1;
+        let at_end = if self.direction() {
+            self.next == last
+        } else {
+            self.next == 0
+        };
+        if at_end {
+            match self.path {
+                PathMode::Linear => return None,
+                PathMode::PingPong => self.forward = !self.forward,
+                PathMode::Loop => return Some(if self.direction() { 0 } else { last }),
+            }
+        }
+        Some(if self.direction() {
+            self.next + 1
+        } else {
+            self.next - 1
+        })
+    }
+}
+
+fn platform_signals(
+    mut events: EventReader<PressurePlateEvent>,
+    mut platforms: Query<&mut Platform>,
+) {
+    for PressurePlateEvent(_, signal, pressed) in events.read() {
+        if let Signal::Custom(i) = signal {
+            for mut platform in platforms.iter_mut() {
+                if let Some((channel, trigger)) = platform.trigger {
+                    if channel == *i {
+                        platform.pressed = if *pressed {
+                            platform.pressed + 1
+                        } else {
+                            platform.pressed.saturating_sub(1)
+                        };
+                        if trigger == PlatformTrigger::Reverse {
+                            let reversed = platform.pressed > 0;
+                            platform.set_reversed(reversed);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn move_platforms(
+    time: Res<Time>,
+    mut platforms: Query<(&mut Platform, &Transform, &mut LinearVelocity)>,
+) {
+    let delta_time = time.delta_seconds();
+    if delta_time <= 0.0 {
+        return;
+    }
+    for (mut platform, transform, mut velocity) in platforms.iter_mut() {
+        if platform.waypoints.len() < 2 || !platform.running() {
+            velocity.0 = Vec2::ZERO;
+            continue;
+        }
+        let diff = platform.waypoints[platform.next] - transform.translation.xy();
+        let step = platform.speed * delta_time;
+        if diff.length() > step {
+            velocity.0 = diff.normalize() * platform.speed;
+        } else if let Some(next) = platform.step() {
+            // Arrive at the waypoint exactly, and continue towards the next one
+            velocity.0 = diff / delta_time;
+            platform.from = platform.next;
+            platform.next = next;
+        } else {
+            velocity.0 = diff / delta_time;
+        }
+    }
+}
+
+/// A static belt that carries the bodies resting on it with its surface velocity.
+/// Creatures roll relative to the belt, so their maximum angular velocity adds to its speed.
+#[derive(Component, Clone)]
+pub struct Conveyor {
+    speed: f32,
+    channel: Option<u16>,
+    pressed: u16,
+}
+
+impl Conveyor {
+    pub fn surface_velocity(&self, transform: &Transform) -> Vec2 {
+        (transform.rotation * Vec3::X).xy() * self.surface_speed()
+    }
+
+    /// The direction is reversed while the `Signal::Custom` channel is pressed.
+    fn surface_speed(&self) -> f32 {
+        if self.pressed > 0 {
+            -self.speed
+        } else {
+            self.speed
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+struct Stripe {}
+
+/// A belt from `start` to `end`, positive speeds move the top surface towards `end`.
+pub fn spawn_conveyor(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    start: Vec2,
+    end: Vec2,
+    thickness: f32,
+    speed: f32,
+    channel: Option<u16>,
+) {
+    let center = start.midpoint(end);
+    let length = start.distance(end);
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: CONVEYOR_COLOR,
+                    custom_size: Some(Vec2::new(length, thickness)),
+                    ..default()
+                },
+                transform: Transform::from_translation(center.extend(-1.0))
+                    .with_rotation(Quat::from_rotation_z((end - start).to_angle())),
+                ..default()
+            },
+            RigidBody::Static,
+            Collider::rectangle(length, thickness),
+            Friction::new(1.0),
+            Conveyor {
+                speed,
+                channel,
+                pressed: 0,
+            },
+        ))
+        .with_children(|cb| {
+            let count = (length / STRIPE_SPACING).floor().max(1.0) as usize;
+            for i in 0..count {
+                let x = ((i as f32 + 0.5) / count as f32 - 0.5) * length;
+                cb.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: STRIPE_COLOR,
+                            custom_size: Some(Vec2::new(4.0, thickness * 0.8)),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(x, 0.0, 0.1),
+                        ..default()
+                    },
+                    Stripe {},
+                ));
+            }
+        });
+}
+
+fn conveyor_signals(
+    mut events: EventReader<PressurePlateEvent>,
+    mut conveyors: Query<&mut Conveyor>,
+) {
+    for PressurePlateEvent(_, signal, pressed) in events.read() {
+        let Signal::Custom(i) = signal else {
+            continue;
+        };
+        for mut conveyor in conveyors.iter_mut() {
+            if conveyor.channel == Some(*i) {
+                conveyor.pressed = if *pressed {
+                    conveyor.pressed + 1
+                } else {
+                    conveyor.pressed.saturating_sub(1)
+                };
+            }
+        }
+    }
+}
+
+/// Speeds up the bodies resting on a belt towards its surface velocity, their joints pull
+/// the rest of the group along.
+fn carry_on_conveyors(
+    time: Res<Time>,
+    collisions: Res<Collisions>,
+    conveyors: Query<(&Conveyor, &Transform)>,
+    mut bodies: Query<(&RigidBody, &mut LinearVelocity)>,
+) {
+    let mut carried = HashSet::new();
+    for contacts in collisions.iter() {
+        // The normals are in the local space of the conveyor
+        let (conveyor, other, normals) = if conveyors.contains(contacts.entity1) {
+            let normals: Vec<Vec2> = contacts.manifolds.iter().map(|m| m.normal1).collect();
+            (contacts.entity1, contacts.entity2, normals)
+        } else if conveyors.contains(contacts.entity2) {
+            let normals: Vec<Vec2> = contacts.manifolds.iter().map(|m| m.normal2).collect();
+            (contacts.entity2, contacts.entity1, normals)
+        } else {
+            continue;
+        };
+        let resting =
+            contacts.total_normal_impulse > 0.0 && normals.iter().all(|n| n.dot(Vec2::Y) >= 0.5);
+        if !resting || !carried.insert(other) {
+            continue;
+        }
+        let (Ok((conveyor, transform)), Ok((rb, mut velocity))) =
+            (conveyors.get(conveyor), bodies.get_mut(other))
+        else {
+            continue;
+        };
+        if !rb.is_dynamic() {
+            continue;
+        }
+        let tangent = (transform.rotation * Vec3::X).xy();
+        let speed = conveyor.surface_speed();
+        // Only ever up to the belt speed, faster bodies are not slowed down
+        let missing = (speed - velocity.dot(tangent)) * speed.signum();
+        if missing > 0.0 {
+            let step = missing.min(CONVEYOR_GRIP * time.delta_seconds());
+            velocity.0 += tangent * step * speed.signum();
+        }
+    }
+}
+
+fn animate_stripes(
+    time: Res<Time>,
+    conveyors: Query<(&Conveyor, &Sprite, &Children)>,
+    mut stripes: Query<&mut Transform, With<Stripe>>,
+) {
+    for (conveyor, sprite, children) in conveyors.iter() {
+        let length = sprite.custom_size.map_or(0.0, |s| s.x);
+        let half = length * 0.5;
+        let mut iter = stripes.iter_many_mut(children);
+        while let Some(mut transform) = iter.fetch_next() {
+            let x = transform.translation.x + conveyor.surface_speed() * time.delta_seconds();
+            transform.translation.x = (x + half).rem_euclid(length) - half;
+        }
+    }
+}
+
+/// Lets bodies pass through from below, can be added to a `plank`, `wall` or `platform`.
+#[derive(Component, Clone, Default)]
+pub struct OneWay {
+    passing: HashSet<Entity>,
+}
+
+pub fn one_way() -> OneWay {
+    OneWay::default()
+}
+
+fn one_way_platforms(
+    mut collisions: ResMut<Collisions>,
+    mut platforms: Query<&mut OneWay>,
+    mut ended: EventWriter<CollisionEnded>,
+) {
+    collisions.retain(|contacts| {
+        // The normals are in the local space of the platform, so rotated planks work too
+        let (mut one_way, other, normals) = if let Ok(one_way) = platforms.get_mut(contacts.entity1)
+        {
+            let normals: Vec<Vec2> = contacts.manifolds.iter().map(|m| m.normal1).collect();
+            (one_way, contacts.entity2, normals)
+        } else if let Ok(one_way) = platforms.get_mut(contacts.entity2) {
+            let normals: Vec<Vec2> = contacts.manifolds.iter().map(|m| m.normal2).collect();
+            (one_way, contacts.entity1, normals)
+        } else {
+            return true;
+        };
+        let penetrating = contacts
+            .manifolds
+            .iter()
+            .any(|m| m.contacts.iter().any(|c| c.penetration > 0.0));
+        let keep = if penetrating && one_way.passing.contains(&other) {
+            // Keep passing through until the body is completely out of the platform
+            false
+        } else if normals.iter().all(|n| n.dot(Vec2::Y) >= 0.5) {
+            one_way.passing.remove(&other);
+            true
+        } else {
+            // Speculative contacts from below would stop the body before it ever penetrates
+            if penetrating {
+                one_way.passing.insert(other);
+            } else {
+                one_way.passing.remove(&other);
+            }
+            false
+        };
+        // Dropped contacts are not reported, so end them here to keep `Grounded` up to date
+        if !keep && contacts.during_previous_frame {
+            ended.send(CollisionEnded(contacts.entity1, contacts.entity2));
+        }
+        keep
+    });
+}
@@ -0,0 +1,176 @@
+use std::f32::consts::PI;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::audio::Sounds;
+use crate::joints::JointGraph;
+use crate::particles::ParticleEffect;
+use crate::utils::StateLocalSpawner;
+
+const PORTAL_DEPTH: f32 = 10.0;
+/// Seconds before a body can use a portal again.
+const PORTAL_COOLDOWN: f32 = 0.5;
+/// Distance between the exit and the closest body of the group, roughly a creature radius.
+const EXIT_MARGIN: f32 = 25.0;
+
+const PORTAL_COLORS: [Color; 2] = [
+    Color::srgba(1.0, 0.55, 0.1, 0.8),
+    Color::srgba(0.2, 0.6, 1.0, 0.8),
+];
+
+pub struct PortalPlugin;
+
+impl Plugin for PortalPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Update, (cool_down, on_portal_enter).chain());
+    }
+}
+
+/// Teleports bodies entering the front (local +y) out of the front of the partner.
+#[derive(Component, Clone, Copy)]
+pub struct Portal {
+    partner: Entity,
+    max_group: usize,
+    color: Color,
+}
+
+#[derive(Component, Clone)]
+struct Teleported(Timer);
+
+fn portal(center: Vec2, rotation: f32, width: f32, color: Color) -> impl Bundle {
+    (
+        SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(Vec2::new(width, PORTAL_DEPTH)),
+                ..default()
+            },
+            transform: Transform::from_translation(center.extend(-0.5))
+                .with_rotation(Quat::from_rotation_z(rotation)),
+            ..default()
+        },
+        RigidBody::Static,
+        Collider::rectangle(width, PORTAL_DEPTH),
+        Sensor,
+    )
+}
+
+/// Two linked portals, groups with more than `max_group` bodies are too big to fit through.
+pub fn spawn_portals(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    (a, a_rotation): (Vec2, f32),
+    (b, b_rotation): (Vec2, f32),
+    width: f32,
+    max_group: usize,
+) {
+    let a = commands
+        .spawn(portal(a, a_rotation, width, PORTAL_COLORS[0]))
+        .id();
+    let b = commands
+        .spawn(portal(b, b_rotation, width, PORTAL_COLORS[1]))
+        .id();
+    commands.entity(a).insert(Portal {
+        partner: b,
+        max_group,
+        color: PORTAL_COLORS[0],
+    });
+    commands.entity(b).insert(Portal {
+        partner: a,
+        max_group,
+        color: PORTAL_COLORS[1],
+    });
+}
+
+fn cool_down(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut teleported: Query<(Entity, &mut Teleported)>,
+) {
+    for (entity, mut timer) in teleported.iter_mut() {
+        if timer.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Teleported>();
+        }
+    }
+}
+
+fn on_portal_enter(
+    mut commands: Commands,
+    mut collision_event_reader: EventReader<CollisionStarted>,
+    portals: Query<(&Portal, &GlobalTransform)>,
+    mut bodies: Query<
+        (
+            &RigidBody,
+            &mut Transform,
+            &mut LinearVelocity,
+            Has<Teleported>,
+        ),
+        Without<Portal>,
+    >,
+    graph: Res<JointGraph>,
+    mut sounds: EventWriter<Sounds>,
+    mut effects: EventWriter<ParticleEffect>,
+) {
+    let mut moved = HashSet::new();
+    for CollisionStarted(e1, e2) in collision_event_reader.read() {
+        for (body, portal) in [(e1, e2), (e2, e1)] {
+            let Ok((portal, entry)) = portals.get(*portal) else {
+                continue;
+            };
+            let Ok((partner, exit)) = portals.get(portal.partner) else {
+                continue;
+            };
+            let Ok((rb, _, velocity, teleported)) = bodies.get(*body) else {
+                continue;
+            };
+            let (_, entry_rotation, entry_center) = entry.to_scale_rotation_translation();
+            let entry_normal = (entry_rotation * Vec3::Y).xy();
+            if !rb.is_dynamic()
+                || teleported
+                || moved.contains(body)
+                || velocity.dot(entry_normal) > 0.0
+            {
+                continue;
+            }
+            let group = graph.group_of(*body);
+            let anchored = group
+                .iter()
+                .any(|e| bodies.get(*e).map_or(true, |b| !b.0.is_dynamic()));
+            if group.len() > portal.max_group || anchored {
+                sounds.send(Sounds::Grunt);
+                continue;
+            }
+            let (_, exit_rotation, exit_center) = exit.to_scale_rotation_translation();
+            // Enter the front and leave from the front, turned around
+            let rotation = exit_rotation * Quat::from_rotation_z(PI) * entry_rotation.inverse();
+            let exit_normal = (exit_rotation * Vec3::Y).xy();
+            let positions: Vec<(Entity, Vec2)> = group
+                .iter()
+                .filter_map(|e| bodies.get(*e).ok().map(|b| (*e, b.1.translation.xy())))
+                .map(|(e, p)| (e, (rotation * (p - entry_center.xy()).extend(0.0)).xy()))
+                .collect();
+            // Push the whole group out in front of the exit
+            let behind = positions
+                .iter()
+                .map(|(_, p)| p.dot(exit_normal))
+                .fold(0.0, f32::min);
+            let offset = exit_center.xy() + exit_normal * (EXIT_MARGIN + PORTAL_DEPTH - behind);
+            effects.send(ParticleEffect::Burst(entry_center.xy(), portal.color));
+            effects.send(ParticleEffect::Burst(exit_center.xy(), partner.color));
+            sounds.send(Sounds::Click);
+            for (e, p) in positions {
+                moved.insert(e);
+                if let Ok((_, mut transform, mut velocity, _)) = bodies.get_mut(e) {
+                    transform.translation = (p + offset).extend(transform.translation.z);
+                    transform.rotation = rotation * transform.rotation;
+                    velocity.0 = (rotation * velocity.0.extend(0.0)).xy();
+                }
+                commands.entity(e).insert(Teleported(Timer::from_seconds(
+                    PORTAL_COOLDOWN,
+                    TimerMode::Once,
+                )));
+            }
+        }
+    }
+}
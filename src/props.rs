@@ -0,0 +1,150 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+
+use crate::utils::StateLocalSpawner;
+
+const CRATE_COLOR: Color = Color::srgb(0.7, 0.5, 0.3);
+const BALL_COLOR: Color = Color::srgb(1.0, 0.35, 0.3);
+const BARREL_COLOR: Color = Color::srgb(0.55, 0.35, 0.2);
+
+pub struct PropPlugin;
+
+impl Plugin for PropPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Startup, setup);
+    }
+}
+
+/// Dynamic objects that can be pushed around and rest on pressure plates.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Prop {
+    Crate,
+    Ball,
+    Barrel,
+}
+
+/// Props that controlled creatures hold on to when touching them, like they join other creatures.
+#[derive(Component, Clone, Copy)]
+pub struct Grabbable {}
+
+impl Prop {
+    fn density(self) -> f32 {
+        match self {
+            Prop::Crate => 1.5,
+            Prop::Ball => 0.3,
+            Prop::Barrel => 2.0,
+        }
+    }
+
+    fn friction(self) -> f32 {
+        match self {
+            Prop::Crate => 1.5,
+            Prop::Ball => 1.0,
+            Prop::Barrel => 0.8,
+        }
+    }
+
+    fn bounciness(self) -> f32 {
+        match self {
+            Prop::Ball => 0.8,
+            _ => 0.1,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct PropAssets {
+    circle_mesh: Handle<Mesh>,
+    ball_material: Handle<ColorMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    commands.insert_resource(PropAssets {
+        circle_mesh: meshes.add(Circle::new(1.0)),
+        ball_material: materials.add(BALL_COLOR),
+    });
+}
+
+/// Spawns a prop with the given size, balls only use the width as diameter.
+pub fn spawn_prop(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    prop: Prop,
+    position: Vec2,
+    size: Vec2,
+    grabbable: bool,
+    assets: &Res<PropAssets>,
+) -> Entity {
+    let mut ec = commands.spawn((
+        SpatialBundle::from_transform(Transform::from_translation(position.extend(0.5))),
+        RigidBody::Dynamic,
+        ColliderDensity(prop.density()),
+        Friction::new(prop.friction()),
+        Restitution::new(prop.bounciness()),
+        prop,
+    ));
+    if grabbable {
+        ec.insert(Grabbable {});
+    }
+    match prop {
+        Prop::Crate | Prop::Barrel => {
+            let collider = if prop == Prop::Crate {
+                Collider::rectangle(size.x, size.y)
+            } else {
+                // Rounded ends so that barrels can be tipped over and rolled
+                let radius = size.x.min(size.y) * 0.25;
+                Collider::round_rectangle(size.x - radius * 2.0, size.y - radius * 2.0, radius)
+            };
+            let color = if prop == Prop::Crate {
+                CRATE_COLOR
+            } else {
+                BARREL_COLOR
+            };
+            ec.insert(collider).with_children(|cb| {
+                cb.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(size),
+                        ..default()
+                    },
+                    ..default()
+                });
+                // A darker band to show the rotation
+                cb.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: color.darker(0.15),
+                        custom_size: Some(Vec2::new(size.x, size.y * 0.2)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                    ..default()
+                });
+            });
+        }
+        Prop::Ball => {
+            let radius = size.x * 0.5;
+            ec.insert(Collider::circle(radius)).with_children(|cb| {
+                cb.spawn(MaterialMesh2dBundle {
+                    mesh: assets.circle_mesh.clone().into(),
+                    material: assets.ball_material.clone(),
+                    transform: Transform::from_scale(Vec3::new(radius, radius, 1.0)),
+                    ..default()
+                });
+                cb.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::WHITE,
+                        custom_size: Some(Vec2::new(size.x * 0.9, size.x * 0.15)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                    ..default()
+                });
+            });
+        }
+    }
+    ec.id()
+}
@@ -0,0 +1,176 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::collectibles::LevelCollectibles;
+use crate::creature::CreatureEvent;
+use crate::levels::Level;
+use crate::objects::PressurePlateEvent;
+use crate::save::SaveData;
+use crate::ui::{spawn_button, Signal, TextStyles};
+use crate::utils::StateLocalSpawner;
+
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+pub struct ResultsPlugin;
+
+impl Plugin for ResultsPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.init_resource::<LevelStats>()
+            .add_systems(Update, (on_transition, count_stats, show_results).chain());
+    }
+}
+
+/// Counted from entering a level until reaching its exit, restarts included.
+#[derive(Resource, Clone, Copy, Default)]
+pub struct LevelStats {
+    time: f32,
+    restarts: u32,
+    switches: u32,
+    shoves: u32,
+    complete: bool,
+}
+
+impl LevelStats {
+    /// The results are shown, and the level is frozen behind them.
+    pub fn complete(&self) -> bool {
+        self.complete
+    }
+}
+
+/// Run condition for player input, which stops once the level is complete.
+pub fn playing(stats: Res<LevelStats>) -> bool {
+    !stats.complete
+}
+
+fn on_transition(
+    mut transitions: EventReader<StateTransitionEvent<Level>>,
+    mut stats: ResMut<LevelStats>,
+    mut time: ResMut<Time<Physics>>,
+) {
+    for transition in transitions.read() {
+        if stats.complete {
+            time.unpause();
+        }
+        // Retrying a completed level starts a fresh run
+        if transition.entered == transition.exited && !stats.complete {
+            stats.restarts += 1;
+        } else {
+            *stats = LevelStats::default();
+        }
+    }
+}
+
+fn count_stats(
+    time: Res<Time>,
+    mut events: EventReader<CreatureEvent>,
+    mut stats: ResMut<LevelStats>,
+) {
+    if stats.complete {
+        events.clear();
+        return;
+    }
+    stats.time += time.delta_seconds();
+    let mut shoved = false;
+    for event in events.read() {
+        match event {
+            CreatureEvent::Switched(_) => stats.switches += 1,
+            CreatureEvent::Shoved(_) => shoved = true,
+            _ => {}
+        }
+    }
+    // Every shoved pair sends events, but it is still a single shove
+    if shoved {
+        stats.shoves += 1;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn show_results(
+    commands: Commands,
+    mut events: EventReader<PressurePlateEvent>,
+    mut stats: ResMut<LevelStats>,
+    collectibles: Res<LevelCollectibles>,
+    state: Res<State<Level>>,
+    mut save: ResMut<SaveData>,
+    mut time: ResMut<Time<Physics>>,
+    text_styles: Res<TextStyles>,
+) {
+    let reached = events.read().any(|PressurePlateEvent(_, signal, pressed)| {
+        matches!(signal, Signal::NextLevel) && *pressed
+    });
+    if !reached || stats.complete {
+        return;
+    }
+    stats.complete = true;
+    time.pause();
+    let previous = save.record_time(*state.get(), stats.time);
+    let best = match previous {
+        Some(best) if best <= stats.time => format!("Personal best: {best:.1} s"),
+        Some(_) => String::from("New personal best!"),
+        None => String::from("First time at this beach!"),
+    };
+    let mut lines = vec![
+        format!("Time: {:.1} s", stats.time),
+        best,
+        format!("Restarts: {}", stats.restarts),
+        format!("Switches: {}", stats.switches),
+        format!("Shoves: {}", stats.shoves),
+    ];
+    if collectibles.total > 0 {
+        lines.push(format!(
+            "Treasures: {} / {}",
+            collectibles.collected, collectibles.total
+        ));
+    }
+
+    StateLocalSpawner(commands)
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            background_color: OVERLAY_COLOR.into(),
+            ..default()
+        })
+        .with_children(|cb| {
+            cb.spawn(TextBundle::from_section(
+                "Level complete!",
+                text_styles.title_text.clone(),
+            ));
+            cb.spawn(
+                TextBundle::from_section(lines.join("\n"), text_styles.sign_text.clone())
+                    .with_text_justify(JustifyText::Center),
+            );
+            cb.spawn(NodeBundle {
+                style: Style {
+                    column_gap: Val::Px(40.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|cb| {
+                spawn_button(
+                    cb,
+                    Signal::RestartLevel,
+                    "Retry",
+                    Val::Px(200.0),
+                    Val::Px(80.0),
+                    &text_styles,
+                );
+                spawn_button(
+                    cb,
+                    Signal::NextLevel,
+                    "Continue",
+                    Val::Px(200.0),
+                    Val::Px(80.0),
+                    &text_styles,
+                );
+            });
+        });
+}
@@ -0,0 +1,209 @@
+use std::collections::VecDeque;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::breakables::Breakable;
+use crate::creature::{Creature, CreatureEvent, MainCreature};
+use crate::levels::Level;
+use crate::objects::{Door, DoorSources};
+use crate::results::playing;
+use crate::utils::StateLocalSpawner;
+
+const REWIND_SECONDS: f32 = 5.0;
+
+const OVERLAY_COLOR: Color = Color::srgba(0.3, 0.5, 1.0, 0.15);
+
+pub struct RewindPlugin;
+
+impl Plugin for RewindPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.init_resource::<Rewind>()
+            .add_systems(Update, reset_rewind)
+            .add_systems(FixedUpdate, (rewind.run_if(playing), record).chain());
+    }
+}
+
+/// The last seconds of play, recorded every fixed tick.
+#[derive(Resource, Default)]
+pub struct Rewind {
+    frames: VecDeque<Frame>,
+    rewinding: bool,
+}
+
+struct Frame {
+    bodies: Vec<(
+        Entity,
+        RigidBody,
+        Transform,
+        LinearVelocity,
+        AngularVelocity,
+    )>,
+    /// Only the joints of creatures, the rest are part of the level.
+    joints: Vec<FixedJoint>,
+    /// The control spreads from the main creature through its group again.
+    main: Option<Entity>,
+    doors: Vec<(Entity, Door)>,
+    breakables: Vec<(Entity, Breakable)>,
+}
+
+#[derive(Component, Clone, Copy)]
+struct RewindOverlay {}
+
+fn reset_rewind(
+    mut transitions: EventReader<StateTransitionEvent<Level>>,
+    mut rewind: ResMut<Rewind>,
+    mut time: ResMut<Time<Physics>>,
+) {
+    if transitions.read().count() > 0 {
+        rewind.frames.clear();
+        if rewind.rewinding {
+            rewind.rewinding = false;
+            time.unpause();
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn record(
+    time: Res<Time<Fixed>>,
+    mut rewind: ResMut<Rewind>,
+    bodies: Query<(
+        Entity,
+        &RigidBody,
+        &Transform,
+        &LinearVelocity,
+        &AngularVelocity,
+    )>,
+    joints: Query<&FixedJoint>,
+    creatures: Query<(), With<Creature>>,
+    main: Query<Entity, (With<MainCreature>, With<Creature>)>,
+    doors: Query<(Entity, &Door)>,
+    breakables: Query<(Entity, &Breakable)>,
+) {
+    if rewind.rewinding {
+        return;
+    }
+    let capacity = (REWIND_SECONDS / time.timestep().as_secs_f32()).ceil() as usize;
+    while rewind.frames.len() >= capacity {
+        rewind.frames.pop_front();
+    }
+    rewind.frames.push_back(Frame {
+        bodies: bodies
+            .iter()
+            .map(|(e, rb, t, v, a)| (e, *rb, *t, *v, *a))
+            .collect(),
+        joints: joints
+            .iter()
+            .filter(|j| creatures.contains(j.entity1) || creatures.contains(j.entity2))
+            .copied()
+            .collect(),
+        main: main.get_single().ok(),
+        doors: doors.iter().map(|(e, d)| (e, d.clone())).collect(),
+        breakables: breakables.iter().map(|(e, b)| (e, *b)).collect(),
+    });
+}
+
+/// Steps back one recorded tick per fixed tick while held, with the physics paused.
+/// Anything that has been despawned (broken or collected) stays gone.
+#[allow(clippy::too_many_arguments)]
+fn rewind(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut rewind: ResMut<Rewind>,
+    mut time: ResMut<Time<Physics>>,
+    joints: Query<(Entity, &FixedJoint)>,
+    creatures: Query<(), With<Creature>>,
+    mut bodies: Query<(
+        &mut RigidBody,
+        &mut Transform,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    mut doors: Query<&mut Door>,
+    sources: DoorSources,
+    mut breakables: Query<&mut Breakable>,
+    overlays: Query<Entity, With<RewindOverlay>>,
+    mut events: EventWriter<CreatureEvent>,
+) {
+    let held = keyboard_input.any_pressed([KeyCode::KeyZ, KeyCode::Delete]);
+    if !held {
+        if rewind.rewinding {
+            rewind.rewinding = false;
+            time.unpause();
+            for overlay in overlays.iter() {
+                commands.entity(overlay).despawn_recursive();
+            }
+            if let Some(main) = rewind.frames.back().and_then(|f| f.main) {
+                events.send(CreatureEvent::Restored(main));
+            }
+        }
+        return;
+    }
+    if !rewind.rewinding {
+        rewind.rewinding = true;
+        time.pause();
+        StateLocalSpawner(commands.reborrow()).spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: OVERLAY_COLOR.into(),
+                ..default()
+            },
+            RewindOverlay {},
+        ));
+    }
+    // Keep the oldest frame, so that letting go continues from there
+    if rewind.frames.len() > 1 {
+        rewind.frames.pop_back();
+    }
+    let Some(frame) = rewind.frames.back() else {
+        return;
+    };
+
+    let current: Vec<(Entity, &FixedJoint)> = joints
+        .iter()
+        .filter(|(_, j)| creatures.contains(j.entity1) || creatures.contains(j.entity2))
+        .collect();
+    let pairs = |j: &FixedJoint| (j.entity1, j.entity2);
+    let recorded: HashSet<(Entity, Entity)> = frame.joints.iter().map(pairs).collect();
+    if current.len() != recorded.len() || current.iter().any(|(_, j)| !recorded.contains(&pairs(j)))
+    {
+        for (entity, _) in current {
+            commands.entity(entity).despawn();
+        }
+        for joint in frame.joints.iter() {
+            if bodies.contains(joint.entity1) && bodies.contains(joint.entity2) {
+                commands.spawn(*joint);
+            }
+        }
+    }
+
+    for (entity, rb, transform, velocity, angular) in frame.bodies.iter() {
+        if let Ok((mut current_rb, mut current, mut current_velocity, mut current_angular)) =
+            bodies.get_mut(*entity)
+        {
+            if *current_rb != *rb {
+                *current_rb = *rb;
+            }
+            *current = *transform;
+            *current_velocity = *velocity;
+            *current_angular = *angular;
+        }
+    }
+    for (entity, door) in frame.doors.iter() {
+        if let Ok(mut current) = doors.get_mut(*entity) {
+            current.restore(door, &sources);
+        }
+    }
+    for (entity, breakable) in frame.breakables.iter() {
+        if let Ok(mut current) = breakables.get_mut(*entity) {
+            *current = *breakable;
+        }
+    }
+}
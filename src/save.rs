@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use enum_iterator::all;
+
+use crate::levels::Level;
+
+/// A plain text file next to the game, without it the progress is only kept until the game closes
+/// (a failed write logs a warning). The web build has no file system and does not save at all.
+#[cfg(not(target_arch = "wasm32"))]
+const SAVE_FILE: &str = "save.txt";
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.insert_resource(SaveData::load());
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LevelRecord {
+    pub collected: u32,
+    pub total: u32,
+    /// Seconds taken to complete the level, including restarts.
+    pub best_time: Option<f32>,
+}
+
+#[derive(Resource, Default)]
+pub struct SaveData {
+    levels: HashMap<Level, LevelRecord>,
+}
+
+impl SaveData {
+    fn load() -> Self {
+        let mut data = Self::default();
+        let Some(text) = read_save() else {
+            return data;
+        };
+        for line in text.lines() {
+            let mut words = line.split_whitespace();
+            let Some(name) = words.next() else {
+                continue;
+            };
+            let Some(level) = all::<Level>().find(|l| format!("{l:?}") == name) else {
+                continue;
+            };
+            let record = data.levels.entry(level).or_default();
+            for (key, value) in words.filter_map(|w| w.split_once('=')) {
+                match (key, value.parse()) {
+                    ("collected", Ok(v)) => record.collected = v,
+                    ("total", Ok(v)) => record.total = v,
+                    ("time", _) => record.best_time = value.parse().ok(),
+                    _ => {}
+                }
+            }
+        }
+        data
+    }
+
+    fn save(&self) {
+        let text: String = self
+            .levels
+            .iter()
+            .map(|(level, r)| {
+                let time = r
+                    .best_time
+                    .map(|t| format!(" time={t}"))
+                    .unwrap_or_default();
+                format!(
+                    "{level:?} collected={} total={}{time}\n",
+                    r.collected, r.total
+                )
+            })
+            .collect();
+        write_save(text);
+    }
+
+    /// Keeps the best number of collectibles found in a single run of the level.
+    pub fn record_collectibles(&mut self, level: Level, collected: u32, total: u32) {
+        let record = self.levels.entry(level).or_default();
+        record.collected = record.collected.max(collected);
+        record.total = total;
+        self.save();
+    }
+
+    /// Returns the previous best time, if there was one.
+    pub fn record_time(&mut self, level: Level, seconds: f32) -> Option<f32> {
+        let record = self.levels.entry(level).or_default();
+        let previous = record.best_time;
+        if !previous.is_some_and(|best| best <= seconds) {
+            record.best_time = Some(seconds);
+            self.save();
+        }
+        previous
+    }
+
+    pub fn total_collected(&self) -> u32 {
+        self.levels.values().map(|r| r.collected).sum()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_save() -> Option<String> {
+    std::fs::read_to_string(SAVE_FILE).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_save(text: String) {
+    if let Err(e) = std::fs::write(SAVE_FILE, text) {
+        warn!("Could not save the progress: {e}");
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_save() -> Option<String> {
+    None
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_save(_text: String) {}
@@ -16,7 +16,8 @@ pub struct UiPlugin;
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.add_systems(Startup, setup)
-            .add_systems(Update, button_system)
+            .add_systems(Update, (button_system, settings_input))
+            .init_resource::<Settings>()
             .add_event::<Signal>();
     }
 }
@@ -24,16 +25,21 @@ impl Plugin for UiPlugin {
 #[derive(Event, Clone, Copy, Debug)]
 pub enum Signal {
     NextLevel,
-    #[expect(dead_code)]
     RestartLevel,
+    /// Sent by checkpoint plates.
+    Checkpoint,
     Door(u16),
-    #[expect(dead_code)]
     Custom(u16),
 }
 
 #[derive(Component, Debug, Clone, Copy)]
 struct ButtonSignal(Signal, bool);
 
+#[derive(Resource, Default)]
+pub struct Settings {
+    pub reduced_motion: bool,
+}
+
 #[derive(Resource)]
 pub struct TextStyles {
     pub sign_text: TextStyle,
@@ -62,6 +68,13 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     });
 }
 
+fn settings_input(keyboard_input: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
+    if keyboard_input.just_pressed(KeyCode::KeyM) {
+        settings.reduced_motion = !settings.reduced_motion;
+    }
+}
+
+#[allow(clippy::type_complexity)]
 fn button_system(
     mut interactions: Query<
         (&Interaction, &mut BorderColor, &mut ButtonSignal),
@@ -124,7 +137,7 @@ pub fn spawn_button(
     text: &str,
     width: Val,
     height: Val,
-    text_styles: Res<TextStyles>,
+    text_styles: &TextStyles,
 ) {
     commands
         .spawn((
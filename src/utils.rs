@@ -62,11 +62,11 @@ pub fn despawn_state_local(mut commands: Commands, query: Query<Entity, With<Sta
 pub struct StateLocalSpawner<'w, 's>(pub Commands<'w, 's>);
 
 impl<'w, 's> StateLocalSpawner<'w, 's> {
-    pub fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands {
+    pub fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands<'_> {
         self.0.spawn((StateLocal {}, bundle))
     }
 
-    pub fn entity(&mut self, entity: Entity) -> EntityCommands {
+    pub fn entity(&mut self, entity: Entity) -> EntityCommands<'_> {
         self.0.entity(entity)
     }
 }
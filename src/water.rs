@@ -0,0 +1,138 @@
+use std::f32::consts::PI;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::creature::Creature;
+use crate::particles::ParticleEffect;
+
+/// Creatures with a lower density float, creatures with a higher density sink.
+const WATER_DENSITY: f32 = 1.5;
+const LINEAR_DRAG: f32 = 2.0;
+const ANGULAR_DRAG: f32 = 3.0;
+const SPLASH_SPEED: f32 = 150.0;
+
+const WATER_COLOR: Color = Color::srgba(0.2, 0.5, 0.9, 0.45);
+
+pub struct WaterPlugin;
+
+impl Plugin for WaterPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Update, (on_water_enter, on_water_exit, buoyancy).chain());
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct Water {
+    surface: f32,
+    bodies: HashSet<Entity>,
+}
+
+pub fn water(topleft: Vec2, bottomright: Vec2) -> impl Bundle {
+    (
+        SpriteBundle {
+            sprite: Sprite {
+                color: WATER_COLOR,
+                custom_size: Some(Vec2::ONE),
+                ..default()
+            },
+            transform: Transform::from_translation(topleft.midpoint(bottomright).extend(1.5))
+                .with_scale((bottomright - topleft).abs().extend(1.0)),
+            ..default()
+        },
+        RigidBody::Static,
+        Collider::rectangle(1.0, 1.0),
+        Sensor,
+        Water {
+            surface: topleft.y.max(bottomright.y),
+            bodies: HashSet::new(),
+        },
+    )
+}
+
+/// The submerged fraction of a circle, from the area of the circular segment below the surface.
+fn submerged(surface: f32, center: f32, radius: f32) -> f32 {
+    let depth = (surface - (center - radius)).clamp(0.0, 2.0 * radius);
+    let d = radius - depth;
+    let area = radius * radius * (d / radius).acos() - d * (radius * radius - d * d).sqrt();
+    area / (PI * radius * radius)
+}
+
+fn splash(
+    effects: &mut EventWriter<ParticleEffect>,
+    water: &Water,
+    transform: &GlobalTransform,
+    velocity: &LinearVelocity,
+) {
+    if velocity.y.abs() > SPLASH_SPEED {
+        effects.send(ParticleEffect::Splash(Vec2::new(
+            transform.translation().x,
+            water.surface,
+        )));
+    }
+}
+
+fn on_water_enter(
+    mut collision_event_reader: EventReader<CollisionStarted>,
+    mut waters: Query<&mut Water>,
+    creatures: Query<(&GlobalTransform, &LinearVelocity), With<Creature>>,
+    mut effects: EventWriter<ParticleEffect>,
+) {
+    for CollisionStarted(e1, e2) in collision_event_reader.read() {
+        for (creature, water) in [(e1, e2), (e2, e1)] {
+            if let Ok(mut water) = waters.get_mut(*water) {
+                if let Ok((transform, velocity)) = creatures.get(*creature) {
+                    water.bodies.insert(*creature);
+                    splash(&mut effects, &water, transform, velocity);
+                }
+            }
+        }
+    }
+}
+
+fn on_water_exit(
+    mut collision_event_reader: EventReader<CollisionEnded>,
+    mut waters: Query<&mut Water>,
+    creatures: Query<(&GlobalTransform, &LinearVelocity), With<Creature>>,
+    mut effects: EventWriter<ParticleEffect>,
+) {
+    for CollisionEnded(e1, e2) in collision_event_reader.read() {
+        for (creature, water) in [(e1, e2), (e2, e1)] {
+            if let Ok(mut water) = waters.get_mut(*water) {
+                if water.bodies.remove(creature) {
+                    if let Ok((transform, velocity)) = creatures.get(*creature) {
+                        splash(&mut effects, &water, transform, velocity);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn buoyancy(
+    time: Res<Time>,
+    gravity: Res<Gravity>,
+    waters: Query<&Water>,
+    mut creatures: Query<(
+        &Creature,
+        &GlobalTransform,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+) {
+    let delta_time = time.delta_seconds();
+    for water in waters.iter() {
+        let mut iter = creatures.iter_many_mut(&water.bodies);
+        while let Some((creature, transform, mut velocity, mut angular)) = iter.fetch_next() {
+            let fraction = submerged(water.surface, transform.translation().y, creature.radius());
+            if fraction <= 0.0 {
+                continue;
+            }
+            // Archimedes: the displaced water pushes up relative to the creature's own density
+            velocity.0 -= gravity.0 * (WATER_DENSITY / creature.density() * fraction * delta_time);
+            velocity.0 *= (1.0 - LINEAR_DRAG * fraction * delta_time).max(0.0);
+            angular.0 *= (1.0 - ANGULAR_DRAG * fraction * delta_time).max(0.0);
+        }
+    }
+}
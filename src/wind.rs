@@ -0,0 +1,235 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::objects::PressurePlateEvent;
+use crate::ui::Signal;
+use crate::utils::StateLocalSpawner;
+
+/// Roughly the mass of a normal creature, which gets exactly the configured strength.
+const REFERENCE_MASS: f32 = 1250.0;
+const FAN_THICKNESS: f32 = 10.0;
+const STREAK_SPACING: f32 = 1500.0;
+const STREAK_SPEED: f32 = 0.5;
+
+const WIND_COLOR: Color = Color::srgba(0.8, 0.9, 1.0, 0.1);
+const STREAK_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.5);
+const FAN_COLOR: Color = Color::srgb(0.4, 0.4, 0.45);
+
+pub struct WindPlugin;
+
+impl Plugin for WindPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(
+            Update,
+            (
+                (on_wind_enter, on_wind_exit, wind_signals, blow).chain(),
+                animate_streaks,
+            ),
+        );
+    }
+}
+
+/// Pushes dynamic bodies along its local x-axis, lighter bodies are pushed further.
+#[derive(Component, Clone)]
+pub struct Wind {
+    size: Vec2,
+    strength: f32,
+    falloff: f32,
+    channel: Option<u16>,
+    pressed: u16,
+    bodies: HashSet<Entity>,
+}
+
+impl Wind {
+    /// Winds with a channel only blow while the `Signal::Custom` channel is pressed.
+    fn active(&self) -> bool {
+        self.channel.is_none() || self.pressed > 0
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+struct Streak {}
+
+/// A fan at the left edge (before rotation) blowing across the `size` area.
+/// The strength is an acceleration for a normal creature and `falloff` (0-1)
+/// weakens it linearly towards the far edge.
+pub fn spawn_wind(
+    commands: &mut StateLocalSpawner<'_, '_>,
+    center: Vec2,
+    size: Vec2,
+    rotation: f32,
+    strength: f32,
+    falloff: f32,
+    channel: Option<u16>,
+) -> Entity {
+    let wind = Wind {
+        size,
+        strength,
+        falloff: falloff.clamp(0.0, 1.0),
+        channel,
+        pressed: 0,
+        bodies: HashSet::new(),
+    };
+    let visibility = if wind.active() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: WIND_COLOR,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_translation(center.extend(-1.2))
+                    .with_rotation(Quat::from_rotation_z(rotation)),
+                ..default()
+            },
+            RigidBody::Static,
+            Collider::rectangle(size.x, size.y),
+            Sensor,
+            wind,
+        ))
+        .with_children(|cb| {
+            cb.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: FAN_COLOR,
+                    custom_size: Some(Vec2::new(FAN_THICKNESS, size.y)),
+                    ..default()
+                },
+                transform: Transform::from_xyz((FAN_THICKNESS - size.x) * 0.5, 0.0, 0.2),
+                ..default()
+            });
+            // Spread the streaks evenly with the golden ratio
+            let count = (size.x * size.y / STREAK_SPACING).ceil() as usize;
+            for i in 0..count {
+                let x = (i as f32 * 0.618_034).fract() - 0.5;
+                let y = (i as f32 + 0.5) / count as f32 - 0.5;
+                cb.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: STREAK_COLOR,
+                            custom_size: Some(Vec2::new(size.x.min(30.0), 2.0)),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(x * size.x, y * size.y, 0.1),
+                        visibility,
+                        ..default()
+                    },
+                    Streak {},
+                ));
+            }
+        })
+        .id()
+}
+
+fn on_wind_enter(
+    mut collision_event_reader: EventReader<CollisionStarted>,
+    mut winds: Query<&mut Wind>,
+    bodies: Query<&RigidBody>,
+) {
+    for CollisionStarted(e1, e2) in collision_event_reader.read() {
+        for (body, wind) in [(e1, e2), (e2, e1)] {
+            if let Ok(mut wind) = winds.get_mut(*wind) {
+                if bodies.get(*body).is_ok_and(|rb| rb.is_dynamic()) {
+                    wind.bodies.insert(*body);
+                }
+            }
+        }
+    }
+}
+
+fn on_wind_exit(
+    mut collision_event_reader: EventReader<CollisionEnded>,
+    mut winds: Query<&mut Wind>,
+) {
+    for CollisionEnded(e1, e2) in collision_event_reader.read() {
+        for (body, wind) in [(e1, e2), (e2, e1)] {
+            if let Ok(mut wind) = winds.get_mut(*wind) {
+                wind.bodies.remove(body);
+            }
+        }
+    }
+}
+
+fn wind_signals(
+    mut events: EventReader<PressurePlateEvent>,
+    mut winds: Query<(&mut Wind, &Children)>,
+    mut streaks: Query<&mut Visibility, With<Streak>>,
+) {
+    for PressurePlateEvent(_, signal, pressed) in events.read() {
+        let Signal::Custom(i) = signal else {
+            continue;
+        };
+        for (mut wind, children) in winds.iter_mut() {
+            if wind.channel != Some(*i) {
+                continue;
+            }
+            wind.pressed = if *pressed {
+                wind.pressed + 1
+            } else {
+                wind.pressed.saturating_sub(1)
+            };
+            let visibility = if wind.active() {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+            let mut iter = streaks.iter_many_mut(children);
+            while let Some(mut v) = iter.fetch_next() {
+                *v = visibility;
+            }
+        }
+    }
+}
+
+fn blow(
+    time: Res<Time>,
+    winds: Query<(&Wind, &GlobalTransform)>,
+    mut bodies: Query<(&GlobalTransform, &Mass, &mut LinearVelocity)>,
+) {
+    let delta_time = time.delta_seconds();
+    for (wind, wind_transform) in winds.iter() {
+        if !wind.active() {
+            continue;
+        }
+        let (_, rotation, center) = wind_transform.to_scale_rotation_translation();
+        let direction = (rotation * Vec3::X).xy();
+        let mut iter = bodies.iter_many_mut(&wind.bodies);
+        while let Some((transform, mass, mut velocity)) = iter.fetch_next() {
+            if mass.0 <= 0.0 {
+                continue;
+            }
+            let along = (transform.translation() - center).xy().dot(direction) / wind.size.x + 0.5;
+            let strength = wind.strength * (1.0 - wind.falloff * along.clamp(0.0, 1.0));
+            velocity.0 += direction * (strength * REFERENCE_MASS / mass.0 * delta_time);
+        }
+    }
+}
+
+fn animate_streaks(
+    time: Res<Time>,
+    winds: Query<(&Wind, &Children)>,
+    mut streaks: Query<&mut Transform, With<Streak>>,
+) {
+    for (wind, children) in winds.iter() {
+        if !wind.active() {
+            continue;
+        }
+        let half = wind.size.x * 0.5;
+        let speed = wind.strength * STREAK_SPEED;
+        let mut iter = streaks.iter_many_mut(children);
+        while let Some(mut transform) = iter.fetch_next() {
+            // Slow down towards the far edge together with the wind
+            let along = (transform.translation.x + half) / wind.size.x;
+            let factor = 1.0 - wind.falloff * along.clamp(0.0, 1.0);
+            transform.translation.x += speed * factor.max(0.2) * time.delta_seconds();
+            if transform.translation.x > half {
+                transform.translation.x -= wind.size.x;
+            }
+        }
+    }
+}